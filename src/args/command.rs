@@ -1,5 +1,5 @@
-use crate::utilities::stdout::{arg_style, command_style};
-use clap::{Args as ClapArgs, ColorChoice, Parser, Subcommand};
+use arc::{arg_style, command_style};
+use clap::{Args as ClapArgs, ColorChoice, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -22,9 +22,17 @@ pub enum Commands {
     #[command(about = command_style("Compile a given file to html"))]
     Compile(CompileArgs),
 
+    #[command(about = command_style("Report every error and warning in a given file"))]
+    Check(CheckArgs),
+
+    #[command(about = command_style("Count the open and done tasks in a given file"))]
+    Stats(StatsArgs),
+
+    #[cfg(feature = "preview")]
     #[command(about = command_style("Render a given file directly inside the browser"))]
     Preview(PreviewArgs),
 
+    #[cfg(feature = "pdf")]
     #[command(about = command_style("Build and save the output to a PDF file"))]
     Build(BuildArgs),
 
@@ -42,8 +50,30 @@ pub struct CompileArgs {
 
     #[arg(short, long, help = arg_style("Path to the output directory"))]
     pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub diagnostics: DiagnosticArgs,
+}
+
+#[derive(ClapArgs)]
+pub struct CheckArgs {
+    #[arg(help = arg_style("Path to the file to check"), default_value = "new.txt")]
+    pub file: PathBuf,
+
+    #[command(flatten)]
+    pub diagnostics: DiagnosticArgs,
+}
+
+#[derive(ClapArgs)]
+pub struct StatsArgs {
+    #[arg(help = arg_style("Path to the file to count tasks in"), default_value = "new.txt")]
+    pub file: PathBuf,
+
+    #[command(flatten)]
+    pub diagnostics: DiagnosticArgs,
 }
 
+#[cfg(feature = "pdf")]
 #[derive(ClapArgs)]
 pub struct BuildArgs {
     #[arg(help = arg_style("Path to the file to build"), default_value = "new.txt")]
@@ -54,14 +84,58 @@ pub struct BuildArgs {
 
     #[arg(short='H', long="from-html", help = arg_style("Whether build from html"))]
     pub html: bool,
+
+    #[command(flatten)]
+    pub diagnostics: DiagnosticArgs,
 }
 
+#[cfg(feature = "preview")]
 #[derive(ClapArgs)]
 pub struct PreviewArgs {
     #[arg(help = arg_style("Path to the rendered file"), default_value = "new.txt")]
     pub file: PathBuf,
     #[arg(short, long, help = arg_style("Whether to enable live reloading"))]
     pub live: bool,
+
+    #[command(flatten)]
+    pub diagnostics: DiagnosticArgs,
+}
+
+#[derive(ClapArgs, Clone, Copy, Default)]
+pub struct DiagnosticArgs {
+    #[arg(long = "deny-warnings", help = arg_style("Fail instead of compiling when any warning is raised"))]
+    pub deny_warnings: bool,
+
+    #[arg(short, long, help = arg_style("Do not print warnings"))]
+    pub quiet: bool,
+
+    #[arg(long = "message-format", value_enum, default_value = "human", help = arg_style("How to print warnings and errors"))]
+    pub message_format: MessageFormat,
+
+    #[arg(long, value_name = "MS", default_value_t = 5000, help = arg_style("Give up compiling after this many milliseconds, 0 for no limit"))]
+    pub timeout: u64,
+}
+
+#[derive(ValueEnum, Clone, Copy, Default, PartialEq)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl Commands {
+    pub fn message_format(&self) -> MessageFormat {
+        match self {
+            Commands::Compile(args) => args.diagnostics.message_format,
+            Commands::Check(args) => args.diagnostics.message_format,
+            Commands::Stats(args) => args.diagnostics.message_format,
+            #[cfg(feature = "preview")]
+            Commands::Preview(args) => args.diagnostics.message_format,
+            #[cfg(feature = "pdf")]
+            Commands::Build(args) => args.diagnostics.message_format,
+            Commands::Help(_) | Commands::Write(_) => MessageFormat::Human,
+        }
+    }
 }
 
 #[derive(ClapArgs)]
@@ -1,25 +1,28 @@
-use crate::lexer::lexer::Lexer;
-use crate::lexer::traits::LexerTrait;
-use crate::parse::parse::Parser;
-use crate::utilities::constants::NAME_REGEX;
+use super::command::{DiagnosticArgs, MessageFormat};
+use arc::{Diagnostics, Document, Options, Report, Severity};
+#[cfg(feature = "pdf")]
 use fancy_regex::Regex;
-use inquire;
+#[cfg(feature = "preview")]
 use std::io::Write;
-use std::marker::Send;
+#[cfg(feature = "preview")]
 use std::net::TcpStream;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "pdf")]
+use std::sync::LazyLock;
 use std::time::Duration;
+#[cfg(feature = "preview")]
 use tokio::fs as async_fs;
 
+#[cfg(feature = "prompt")]
 pub fn confirm_overwrite(path: &PathBuf) -> Result<(), String> {
     if path.exists() {
         let confirm = inquire::Confirm::new(&format!(
             "File {} already exists, overwrite?",
             path.display()
         ))
-            .with_default(false)
-            .prompt()
-            .map_err(|e| format!("Failed to confirm overwrite: {}", e))?;
+        .with_default(false)
+        .prompt()
+        .map_err(|e| format!("Failed to confirm overwrite: {}", e))?;
         if !confirm {
             return Err(format!(
                 "File {} already exists, aborting operation.",
@@ -30,6 +33,18 @@ pub fn confirm_overwrite(path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+/// Without the `prompt` feature there is no one to ask, so existing files are kept.
+#[cfg(not(feature = "prompt"))]
+pub fn confirm_overwrite(path: &PathBuf) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!(
+            "File {} already exists, aborting operation.",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
 // pub async fn handle_ws(stream: TcpStream, mut reload_rx: tokio::sync::broadcast::Receiver<()>) {
 //     let ws_stream = accept_async(stream).await.unwrap();
 //     let (mut ws_sender, _) = ws_stream.split();
@@ -70,35 +85,74 @@ pub fn confirm_overwrite(path: &PathBuf) -> Result<(), String> {
 //
 //     Ok(())
 // }
-pub async fn timeout<T: Send + 'static>(
-    task: impl FnOnce() -> Result<T, String> + Send + 'static,
-    duration: u64,
-) -> Result<T, String> {
-    let timeout_duration = Duration::from_millis(duration);
-    let actual_task = tokio::task::spawn_blocking(move || task());
-    let result = tokio::time::timeout(timeout_duration, actual_task)
-        .await
-        .map_err(|_| {
-            format!(
-                "Task timed out after {:.2} seconds",
-                timeout_duration.as_secs_f64()
-            )
-        })?
-        .map_err(|e| format!("Task panicked: {}", e))??;
-    Ok(result)
+/// Lexes and parses `src` within `options.timeout`, then reports every collected error
+/// and warning according to `options`. Fails if any error was recovered from along the way.
+pub fn compile_document(
+    source: &Path,
+    src: String,
+    options: &DiagnosticArgs,
+) -> Result<Document, Report> {
+    let name = source.display().to_string();
+    let compile_options = Options {
+        timeout: (options.timeout > 0).then(|| Duration::from_millis(options.timeout)),
+        ..Options::default()
+    };
+    let document = arc::compile(&src, &compile_options)
+        .map_err(|e| Report::from(e).with_source(&name, &src))?;
+    emit_diagnostics(document.diagnostics(), options, &name, &src)?;
+    Ok(document)
 }
-async fn serve_html(source: &PathBuf) -> Result<String, String> {
+
+pub fn emit_diagnostics(
+    diagnostics: &Diagnostics,
+    options: &DiagnosticArgs,
+    name: &str,
+    src: &str,
+) -> Result<(), Report> {
+    for diagnostic in diagnostics.iter() {
+        match options.message_format {
+            MessageFormat::Json => println!("{}", diagnostic.to_json(Some(name))),
+            MessageFormat::Human if options.quiet && diagnostic.severity == Severity::Warning => {}
+            MessageFormat::Human => eprintln!(
+                "{}\n",
+                Report::from(diagnostic.clone())
+                    .with_source(name, src)
+                    .render()
+            ),
+        }
+    }
+    let errors = diagnostics.errors().count();
+    if errors > 0 {
+        return Err(format!("Aborting due to {} error(s)", errors).into());
+    }
+    let warnings = diagnostics.warnings().count();
+    if options.deny_warnings && warnings > 0 {
+        return Err(format!(
+            "Aborting due to {} warning(s), `--deny-warnings` is set",
+            warnings
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "preview")]
+async fn serve_html(source: &PathBuf, options: &DiagnosticArgs) -> Result<String, Report> {
     let src = async_fs::read_to_string(&source)
         .await
         .map_err(|e| format!("Failed to read file {:?}: {}", source, e))?;
-    let tokens = timeout(|| Lexer::new(src).tokenize(), 5000).await?;
-    let document = timeout(|| Parser::new(tokens).parse(), 5000).await?;
+    let document = compile_document(source, src, options)?;
     let html = document.build();
     Ok(html)
 }
 
-pub async fn handle_request(stream: &mut TcpStream, source: &PathBuf) -> Result<(), String> {
-    let html = serve_html(source).await?;
+#[cfg(feature = "preview")]
+pub async fn handle_request(
+    stream: &mut TcpStream,
+    source: &PathBuf,
+    options: &DiagnosticArgs,
+) -> Result<(), Report> {
+    let html = serve_html(source, options).await?;
     let body = html.as_bytes();
     let headers = format!(
         "HTTP/1.1 200 OK\r\n\
@@ -119,6 +173,7 @@ pub async fn handle_request(stream: &mut TcpStream, source: &PathBuf) -> Result<
     Ok(())
 }
 
+#[cfg(feature = "pdf")]
 pub fn remove_style_for_pdf(html: String) -> String {
     html.replace("&nbsp;", " ")
         .replace(r"\", r"\\")
@@ -129,9 +184,14 @@ pub fn remove_style_for_pdf(html: String) -> String {
         .replace("\t", r"\t")
 }
 
+#[cfg(feature = "pdf")]
+static NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<meta\s+name\s*=\s*([^>\s]+)\s*\/?>").expect("Hard coded regex should be valid.")
+});
+
+#[cfg(feature = "pdf")]
 pub fn find_name_from_txt(html: &str) -> Result<Option<String>, String> {
-    let regex = Regex::new(NAME_REGEX).expect("Hard coded regex should be valid.");
-    let captures = regex
+    let captures = NAME
         .captures(html)
         .map_err(|e| format!("Failed to capture: {}", e))?;
 
@@ -1,37 +1,45 @@
+use super::command::{DiagnosticArgs, MessageFormat};
+use super::helper::compile_document;
 use super::helper::confirm_overwrite;
+#[cfg(feature = "pdf")]
 use super::helper::find_name_from_txt;
+#[cfg(feature = "preview")]
 use super::helper::handle_request;
+#[cfg(feature = "pdf")]
 use super::helper::remove_style_for_pdf;
-use super::helper::timeout;
 use crate::args::command::Args;
-use crate::lexer::lexer::Lexer;
-use crate::lexer::traits::LexerTrait as _;
-use crate::parse::meta::MetaProperties;
-use crate::parse::parse::Parser;
+#[cfg(feature = "preview")]
 use crate::show_err;
-use crate::utilities::constants::STD_LIB_DIRECTORY;
-use crate::utilities::lib::ce::CE_CONTENT;
-use crate::utilities::lib::fmt::FMT_CONTENT;
-use crate::utilities::lib::math::MATH_CONTENT;
-use crate::utilities::stdout::show_success;
+use arc::{Report, STD_LIB, STD_LIB_DIRECTORY, show_success};
 use clap::CommandFactory as _;
+#[cfg(feature = "pdf")]
 use headless_chrome::{Browser, LaunchOptionsBuilder};
+use std::fs;
+#[cfg(feature = "preview")]
 use std::net::Shutdown;
+#[cfg(feature = "preview")]
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "preview")]
 use std::process::Command;
+#[cfg(feature = "preview")]
 use std::sync::mpsc;
+#[cfg(feature = "preview")]
+use std::thread;
+#[cfg(feature = "preview")]
 use std::time::Duration;
-use std::{fs, thread};
 use tokio::fs as async_fs;
 
-pub async fn compile(source: PathBuf, output_path: Option<PathBuf>) -> Result<(), String> {
+pub async fn compile(
+    source: PathBuf,
+    output_path: Option<PathBuf>,
+    options: DiagnosticArgs,
+) -> Result<(), Report> {
     let src = async_fs::read_to_string(&source)
         .await
         .map_err(|e| format!("Failed to read file {:?}: {}", source, e))?;
     let src_clone = src.clone();
-    let tokens = timeout(|| Lexer::new(src).tokenize(), 5000).await?;
-    let document = timeout(|| Parser::new(tokens).parse(), 5000).await?;
+    let document = compile_document(&source, src, &options)?;
     let html = document.build();
 
     if let Some(output_path) = output_path {
@@ -39,20 +47,18 @@ pub async fn compile(source: PathBuf, output_path: Option<PathBuf>) -> Result<()
         async_fs::write(&output_path, html)
             .await
             .map_err(|e| format!("Failed to write to file: {}", e))?;
-        show_success(&format!(
-            "Success! HTML saved to {}",
-            &output_path.display()
-        ));
+        if options.message_format == MessageFormat::Human {
+            show_success(&format!(
+                "Success! HTML saved to {}",
+                &output_path.display()
+            ));
+        }
         return Ok(());
     }
 
     let parent = source.parent().unwrap_or(Path::new(""));
-    let source_name = if let Some(MetaProperties::Name(name)) = document
-        .meta
-        .iter()
-        .find(|m| matches!(m, MetaProperties::Name(_)))
-    {
-        &name
+    let source_name = if let Some(name) = document.name() {
+        name
     } else {
         source
             .file_stem()
@@ -68,18 +74,60 @@ pub async fn compile(source: PathBuf, output_path: Option<PathBuf>) -> Result<()
         &output_path,
         format!("<!-- {} -->\n{}", src_clone, html.replace("\n", "")),
     )
+    .await
+    .map_err(|e| format!("Failed to write to file: {}", e))?;
+
+    if options.message_format == MessageFormat::Human {
+        show_success(&format!(
+            "Success! HTML saved to {}",
+            &output_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn check(source: PathBuf, options: DiagnosticArgs) -> Result<(), Report> {
+    let src = async_fs::read_to_string(&source)
         .await
-        .map_err(|e| format!("Failed to write to file: {}", e))?;
+        .map_err(|e| format!("Failed to read file {:?}: {}", source, e))?;
+    let document = compile_document(&source, src, &options)?;
 
-    show_success(&format!(
-        "Success! HTML saved to {}",
-        &output_path.display()
-    ));
+    if options.message_format == MessageFormat::Human {
+        if document.diagnostics().is_empty() {
+            show_success(&format!("No problems found in {}", source.display()));
+        } else {
+            show_success(&format!(
+                "Finished checking {} with {} warning(s)",
+                source.display(),
+                document.diagnostics().warnings().count()
+            ));
+        }
+    }
+    Ok(())
+}
 
+pub async fn stats(source: PathBuf, options: DiagnosticArgs) -> Result<(), Report> {
+    let src = async_fs::read_to_string(&source)
+        .await
+        .map_err(|e| format!("Failed to read file {:?}: {}", source, e))?;
+    let document = compile_document(&source, src, &options)?;
+    let tasks = document.tasks();
+
+    match options.message_format {
+        MessageFormat::Human => show_success(&format!(
+            "{} open and {} done task(s) in {}",
+            tasks.open,
+            tasks.done,
+            source.display()
+        )),
+        MessageFormat::Json => println!(r#"{{"open":{},"done":{}}}"#, tasks.open, tasks.done),
+    }
     Ok(())
 }
 
-pub async fn render(source: PathBuf) -> Result<(), String> {
+#[cfg(feature = "preview")]
+pub async fn render(source: PathBuf, options: DiagnosticArgs) -> Result<(), Report> {
     let listener =
         TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Failed to bind to port: {}", e))?;
 
@@ -107,7 +155,8 @@ pub async fn render(source: PathBuf) -> Result<(), String> {
 
             match listener.accept() {
                 Ok((mut stream, _)) => {
-                    let res = rt_handle.block_on(handle_request(&mut stream, &source_clone));
+                    let res =
+                        rt_handle.block_on(handle_request(&mut stream, &source_clone, &options));
                     show_err(res);
 
                     let _ = stream.shutdown(Shutdown::Both);
@@ -148,11 +197,13 @@ pub async fn render(source: PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(feature = "pdf")]
 pub async fn build(
     source: PathBuf,
     output_path: Option<PathBuf>,
     from_html: bool,
-) -> Result<(), String> {
+    options: DiagnosticArgs,
+) -> Result<(), Report> {
     let src = async_fs::read_to_string(&source)
         .await
         .map_err(|e| format!("Failed to read file {:?}: {}", source, e))?;
@@ -160,8 +211,8 @@ pub async fn build(
     let (html, document) = if from_html {
         (src, None)
     } else {
-        let tokens = timeout(|| Lexer::new(src).tokenize(), 5000).await?;
-        let document = timeout(|| Parser::new(tokens).parse(), 5000).await?;
+        let mut document = compile_document(&source, src, &options)?;
+        document.expand_details_for_pdf();
         (document.build(), Some(document))
     };
 
@@ -175,11 +226,7 @@ pub async fn build(
         }
     } else {
         if let Some(document) = document {
-            if let Some(MetaProperties::Name(name)) = document
-                .meta
-                .iter()
-                .find(|m| matches!(m, MetaProperties::Name(_)))
-            {
+            if let Some(name) = document.name() {
                 PathBuf::from(name).with_extension("pdf")
             } else {
                 let parent = source.parent().unwrap_or(Path::new(""));
@@ -212,7 +259,7 @@ pub async fn build(
             .build()
             .map_err(|e| format!("Failed to build launch options: {}", e))?,
     )
-        .map_err(|e| format!("Failed to launch browser: {}", e))?;
+    .map_err(|e| format!("Failed to launch browser: {}", e))?;
 
     let tab = browser
         .new_tab()
@@ -252,12 +299,14 @@ pub async fn build(
             )
         })?;
 
-    show_success(&format!("PDF saved to {}", pdf_output_path.display()));
+    if options.message_format == MessageFormat::Human {
+        show_success(&format!("PDF saved to {}", pdf_output_path.display()));
+    }
 
     Ok(())
 }
 
-pub fn write(command: Option<PathBuf>) -> Result<(), String> {
+pub fn write(command: Option<PathBuf>) -> Result<(), Report> {
     if let Some(command) = command {
         let ending = command
             .file_stem()
@@ -269,23 +318,21 @@ pub fn write(command: Option<PathBuf>) -> Result<(), String> {
             fs::read_to_string(&command)
                 .map_err(|e| format!("Failed to read file {:?}: {}", command, e))?,
         )
-            .map_err(|e| format!("Failed to write to file {:?}: {}", command, e))?;
+        .map_err(|e| format!("Failed to write to file {:?}: {}", command, e))?;
 
         show_success(&format!("File written to {:?}", command));
         Ok(())
     } else {
-        fs::write(format!("{}/fmt.txt", STD_LIB_DIRECTORY), FMT_CONTENT)
-            .map_err(|e| format!("Failed to write to file {}: {}", STD_LIB_DIRECTORY, e))?;
-        fs::write(format!("{}/math.txt", STD_LIB_DIRECTORY), MATH_CONTENT)
-            .map_err(|e| format!("Failed to write to file {}: {}", STD_LIB_DIRECTORY, e))?;
-        fs::write(format!("{}/ce.txt", STD_LIB_DIRECTORY), CE_CONTENT)
-            .map_err(|e| format!("Failed to write to file {}: {}", STD_LIB_DIRECTORY, e))?;
+        for (name, content) in STD_LIB {
+            fs::write(format!("{}/{}.txt", STD_LIB_DIRECTORY, name), content)
+                .map_err(|e| format!("Failed to write to file {}: {}", STD_LIB_DIRECTORY, e))?;
+        }
         show_success("Standard library updated!");
         Ok(())
     }
 }
 
-pub fn help(command: Option<String>) -> Result<(), String> {
+pub fn help(command: Option<String>) -> Result<(), Report> {
     match command.as_deref() {
         Some("compile") => {
             Args::command()
@@ -295,6 +342,23 @@ pub fn help(command: Option<String>) -> Result<(), String> {
                 .map_err(|e| format!("Failed to print help: {}", e))?;
             Ok(())
         }
+        Some("check") => {
+            Args::command()
+                .find_subcommand_mut("check")
+                .ok_or("Failed to find subcommand `check`")?
+                .print_help()
+                .map_err(|e| format!("Failed to print help: {}", e))?;
+            Ok(())
+        }
+        Some("stats") => {
+            Args::command()
+                .find_subcommand_mut("stats")
+                .ok_or("Failed to find subcommand `stats`")?
+                .print_help()
+                .map_err(|e| format!("Failed to print help: {}", e))?;
+            Ok(())
+        }
+        #[cfg(feature = "preview")]
         Some("preview") => {
             Args::command()
                 .find_subcommand_mut("preview")
@@ -303,6 +367,7 @@ pub fn help(command: Option<String>) -> Result<(), String> {
                 .map_err(|e| format!("Failed to print help: {}", e))?;
             Ok(())
         }
+        #[cfg(feature = "pdf")]
         Some("build") => {
             Args::command()
                 .find_subcommand_mut("build")
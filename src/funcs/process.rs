@@ -1,126 +1,188 @@
 use crate::funcs::structs::{FullFunction, Function as _, InlineFunction, MultiLineFunction};
+use crate::lexer::span::{SourceText, Span};
 use crate::utilities::constants::STD_LIB_DIRECTORY;
-use crate::utilities::constants::{
-    COMMENT_REGEX, FULL_FUNC_REGEX, IMPORT_REGEX, SCRIPT_REGEX, SHORT_FUNC_REGEX, MULTI_LINE_FN_REGEX,
-};
-use fancy_regex::{Captures, Regex};
+use crate::utilities::deadline::Deadline;
+use crate::utilities::diagnostics::Diagnostics;
+use crate::utilities::error::ArcError;
+use crate::utilities::regexes;
+use fancy_regex::Captures;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub struct FunctionProcessor {
     full_functions: Vec<FullFunction>,
     inline_functions: Vec<InlineFunction>,
     multi_line_functions: Vec<MultiLineFunction>,
-    content: String,
+    content: SourceText,
+    script_span: Option<Span>,
+    diagnostics: Diagnostics,
+    deadline: Deadline,
+    stdlib: PathBuf,
+    safe_mode: bool,
 }
 
 impl FunctionProcessor {
-    pub fn new(content: String) -> Self {
+    pub fn new(content: impl Into<SourceText>) -> Self {
         Self {
             full_functions: Vec::new(),
             inline_functions: Vec::new(),
             multi_line_functions: Vec::new(),
-            content,
+            content: content.into(),
+            script_span: None,
+            diagnostics: Diagnostics::new(),
+            deadline: Deadline::none(),
+            stdlib: PathBuf::from(STD_LIB_DIRECTORY),
+            safe_mode: false,
         }
     }
-    pub fn process(mut self) -> Result<String, String> {
+
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Resolves `@include <std/...>` against `stdlib` instead of the default directory.
+    pub fn with_stdlib(mut self, stdlib: PathBuf) -> Self {
+        self.stdlib = stdlib;
+        self
+    }
+
+    /// In safe mode `@include` is refused with a warning, so the source cannot read files.
+    pub fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Expands every function call. The result keeps track of where each byte
+    /// came from in the original source, and comes with the warnings raised on the way.
+    pub fn process(mut self) -> Result<(SourceText, Diagnostics), ArcError> {
         let mut script_content = self.extract_script_content()?;
-        self.full_functions = Self::extract_full_functions(&mut script_content)?;
-        self.inline_functions = Self::extract_inline_functions(&mut script_content)?;
-        self.multi_line_functions =
-            Self::extract_multi_line_functions(&mut script_content)?;
+        self.extract_functions(&mut script_content)
+            .map_err(|e| self.at_script(e))?;
 
         if !(script_content.trim().is_empty()) {
-            return Err(format!(
+            let error = ArcError::function(format!(
                 "Script content is not fully consumed, suggest invalid function syntax. Reminder: {}",
                 script_content.trim()
-            ));
+            ))
+            .with_help("functions are defined as `fn $name(*a *b): body` or `|*$name| body`");
+            return Err(self.at_script(error));
         }
         // todo: avoid cascading replacement in functions iterations
         for func in self.full_functions {
+            self.deadline.check()?;
             func.invoke(&mut self.content)?;
         }
         for func in self.inline_functions {
+            self.deadline.check()?;
             func.invoke(&mut self.content)?;
         }
         for func in self.multi_line_functions {
+            self.deadline.check()?;
             func.invoke(&mut self.content)?;
         }
-        Ok(self.content)
+        Ok((self.content, self.diagnostics))
+    }
+
+    fn extract_functions(&mut self, script_content: &mut String) -> Result<(), ArcError> {
+        let diagnostics = &mut self.diagnostics;
+        self.full_functions = Self::extract_full_functions(script_content, diagnostics)?;
+        self.inline_functions = Self::extract_inline_functions(script_content, diagnostics)?;
+        self.multi_line_functions =
+            Self::extract_multi_line_functions(script_content, diagnostics)?;
+        Ok(())
+    }
+
+    fn at_script(&self, error: ArcError) -> ArcError {
+        match self.script_span {
+            Some(span) => error.at(span),
+            None => error,
+        }
     }
 
-    fn extract_script_content(&mut self) -> Result<String, String> {
-        let script_regex = Regex::new(SCRIPT_REGEX).expect("Hard coded regex should be valid.");
-        let import_regex = Regex::new(IMPORT_REGEX).expect("Hard coded regex should be valid.");
-        let comment_regex = Regex::new(COMMENT_REGEX).expect("Hard coded regex should be valid.");
+    fn extract_script_content(&mut self) -> Result<String, ArcError> {
         let mut fancy_output: Vec<String> = Vec::new();
-        let mut matches = script_regex.captures_iter(&self.content);
+        let mut matches = regexes::SCRIPT.captures_iter(&self.content.text);
 
         loop {
             match matches.next() {
                 Some(Ok(m)) => {
+                    let whole = m.get(0).expect("Capture group 0 should always exist.");
+                    let span = self.content.span(whole.start(), whole.end());
+                    self.script_span = Some(self.script_span.map_or(span, |s| s.to(&span)));
                     let capture = m
                         .get(1)
                         .expect("Hard coded regex should have a capture group.")
                         .as_str();
-                    fancy_output.push(Self::handle_import(
-                        capture,
-                        &import_regex,
-                        &comment_regex,
-                        &script_regex,
-                    ));
+                    let content = if self.safe_mode {
+                        Self::refuse_import(capture, span, &mut self.diagnostics)
+                    } else {
+                        Self::handle_import(capture, span, &self.stdlib, &mut self.diagnostics)
+                    };
+                    fancy_output.push(content);
                 }
                 None => break,
-                Some(Err(e)) => return Err(format!("Regex error: {}", e)),
+                Some(Err(e)) => return Err(ArcError::function(format!("Regex error: {}", e))),
             }
         }
-        let updated = script_regex.replace_all(&self.content, "").to_string();
-        self.content = updated;
+        self.content
+            .replace_all(&regexes::SCRIPT, |_, _| String::new())
+            .map_err(|e| ArcError::function(format!("Regex error: {}", e)))?;
         Ok(fancy_output.join("\n"))
     }
 
+    fn refuse_import(content: &str, span: Span, diagnostics: &mut Diagnostics) -> String {
+        regexes::IMPORT
+            .replace_all(content, |_: &Captures<'_>| {
+                diagnostics.warn(
+                    ArcError::function("`@include` is disabled in safe mode")
+                        .at(span)
+                        .with_help("define the functions inside the `<script>` block instead"),
+                );
+                String::new()
+            })
+            .to_string()
+    }
+
     fn handle_import(
         content: &str,
-        import_regex: &Regex,
-        comment_regex: &Regex,
-        script_regex: &Regex,
+        span: Span,
+        stdlib: &Path,
+        diagnostics: &mut Diagnostics,
     ) -> String {
-        import_regex
+        regexes::IMPORT
             .replace_all(content, |capture: &Captures<'_>| {
                 let path = capture
                     .get(1)
                     .expect("Hard coded regex should have a capture group.")
                     .as_str();
-                Self::read_import(path, comment_regex, script_regex)
+                Self::read_import(path, stdlib).unwrap_or_else(|warning| {
+                    diagnostics.warn(warning.at(span));
+                    String::new()
+                })
             })
             .to_string()
     }
 
-    fn read_import(path: &str, comment_regex: &Regex, script_regex: &Regex) -> String {
-        let path = if path.starts_with("std/") {
-            PathBuf::from(format!(
-                "{}{}.txt",
-                STD_LIB_DIRECTORY,
-                path.strip_prefix("std/")
-                    .expect("Checked path should start with std/")
-            ))
-        } else {
-            PathBuf::from(path)
+    fn read_import(path: &str, stdlib: &Path) -> Result<String, ArcError> {
+        let path = match path.strip_prefix("std/") {
+            Some(name) => stdlib.join(format!("{}.txt", name)),
+            None => PathBuf::from(path),
         };
         if !path.exists() {
-            crate::warn!(
-                "Runtime Warning: Import path does not exist: {:?}, recheck import path or try arc write for stdlib importation.",
-                path
+            return Err(
+                ArcError::function(format!("Import path does not exist: {:?}", path)).with_help(
+                    "recheck the import path, or run `arc write` to install the standard library",
+                ),
             );
-            return String::new();
         }
-        let string =
-            fs::read_to_string(path).expect("Failed to read import file, path should exist.");
+        let string = fs::read_to_string(&path)
+            .map_err(|e| ArcError::function(format!("Failed to read import {:?}: {}", path, e)))?;
 
-        let reminder = comment_regex.replace_all(&string, "").to_string();
+        let reminder = regexes::COMMENT.replace_all(&string, "").to_string();
 
         let mut fancy_output: Vec<String> = Vec::new();
-        let mut matches = script_regex.captures_iter(&reminder);
+        let mut matches = regexes::SCRIPT.captures_iter(&reminder);
 
         loop {
             match matches.next() {
@@ -132,17 +194,17 @@ impl FunctionProcessor {
                     fancy_output.push(String::from(capture));
                 }
                 None => break,
-                Some(Err(e)) => {
-                    crate::warn!("Runtime Warning: Regex error: {}", e);
-                    break;
-                }
+                Some(Err(e)) => return Err(ArcError::function(format!("Regex error: {}", e))),
             }
         }
-        fancy_output.join("\n")
+        Ok(fancy_output.join("\n"))
     }
 
-    fn extract_full_functions(content: &mut String) -> Result<Vec<FullFunction>, String> {
-        let regex = Regex::new(FULL_FUNC_REGEX).expect("Hard coded regex should be valid.");
+    fn extract_full_functions(
+        content: &mut String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<FullFunction>, ArcError> {
+        let regex = &*regexes::FULL_FUNC;
         let mut full_functions: Vec<FullFunction> = Vec::new();
         let mut matches = regex.captures_iter(content);
         loop {
@@ -164,19 +226,23 @@ impl FunctionProcessor {
                         String::from(name),
                         String::from(args),
                         String::from(body),
+                        diagnostics,
                     )?;
                     full_functions.push(function);
                 }
                 None => break,
-                Some(Err(e)) => return Err(format!("Regex error: {}", e)),
+                Some(Err(e)) => return Err(ArcError::function(format!("Regex error: {}", e))),
             }
         }
         *content = regex.replace_all(content, "").to_string();
         Ok(full_functions)
     }
 
-    fn extract_inline_functions(content: &mut String) -> Result<Vec<InlineFunction>, String> {
-        let regex = Regex::new(SHORT_FUNC_REGEX).expect("Hard coded regex should be valid.");
+    fn extract_inline_functions(
+        content: &mut String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<InlineFunction>, ArcError> {
+        let regex = &*regexes::SHORT_FUNC;
         let mut inline_functions: Vec<InlineFunction> = Vec::new();
         let mut matches = regex.captures_iter(content);
         loop {
@@ -194,19 +260,23 @@ impl FunctionProcessor {
                         String::from(name),
                         format!("*{}", name), // info: because of regex, add this * safely
                         String::from(body),
+                        diagnostics,
                     )?;
                     inline_functions.push(function);
                 }
                 None => break,
-                Some(Err(e)) => return Err(format!("Regex error: {}", e)),
+                Some(Err(e)) => return Err(ArcError::function(format!("Regex error: {}", e))),
             }
         }
         *content = regex.replace_all(content, "").to_string();
         Ok(inline_functions)
     }
-    
-    fn extract_multi_line_functions(content: &mut String) -> Result<Vec<MultiLineFunction>, String> {
-        let regex = Regex::new(MULTI_LINE_FN_REGEX).expect("Hard coded regex should be valid.");
+
+    fn extract_multi_line_functions(
+        content: &mut String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<MultiLineFunction>, ArcError> {
+        let regex = &*regexes::MULTI_LINE_FN;
         let mut multi_line_functions: Vec<MultiLineFunction> = Vec::new();
         let mut matches = regex.captures_iter(content);
         loop {
@@ -228,14 +298,15 @@ impl FunctionProcessor {
                         String::from(name),
                         String::from(args),
                         String::from(body),
+                        diagnostics,
                     )?;
                     multi_line_functions.push(function);
                 }
                 None => break,
-                Some(Err(e)) => return Err(format!("Regex error: {}", e)),
+                Some(Err(e)) => return Err(ArcError::function(format!("Regex error: {}", e))),
             }
         }
         *content = regex.replace_all(content, "").to_string();
         Ok(multi_line_functions)
-    } 
+    }
 }
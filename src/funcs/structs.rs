@@ -1,11 +1,19 @@
+use crate::lexer::span::SourceText;
+use crate::utilities::diagnostics::Diagnostics;
+use crate::utilities::error::ArcError;
 use fancy_regex::{self as regex, Captures, Regex};
 use uuid::Uuid;
 
 pub trait Function {
-    fn new(name: String, params: String, content: String) -> Result<Self, String>
+    fn new(
+        name: String,
+        params: String,
+        content: String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ArcError>
     where
         Self: Sized;
-    fn invoke(&self, content: &mut String) -> Result<(), String>;
+    fn invoke(&self, content: &mut SourceText) -> Result<(), ArcError>;
 }
 
 pub struct FullFunction {
@@ -27,16 +35,24 @@ pub struct MultiLineFunction {
 }
 
 impl Function for FullFunction {
-    fn new(name: String, params: String, content: String) -> Result<Self, String> {
+    fn new(
+        name: String,
+        params: String,
+        content: String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ArcError> {
         let params = Self::parse_args(params)?;
         if name.trim().is_empty() {
-            return Err(format!("Invalid function name: {}, cannot be empty", name));
+            return Err(ArcError::function(format!(
+                "Invalid function name: {}, cannot be empty",
+                name
+            )));
         }
         if !name.trim().starts_with("$") {
-            crate::warn!(
-                "Runtime Warning: function name should start with '$', got {}",
+            diagnostics.warn(ArcError::function(format!(
+                "function name should start with `$`, got {}",
                 name
-            );
+            )));
         }
         Ok(FullFunction {
             params,
@@ -45,76 +61,83 @@ impl Function for FullFunction {
                 .expect("Generated regex should be valid."),
         })
     }
-    fn invoke(&self, content: &mut String) -> Result<(), String> {
-        let mut validation_error: Option<String> = None;
-        let result = self.regex.replace_all(content, |capture: &Captures<'_>| {
-            let args = capture
-                .get(1)
-                .expect("Generated regex should have a capture group.")
-                .as_str();
+    fn invoke(&self, content: &mut SourceText) -> Result<(), ArcError> {
+        let mut validation_error: Option<ArcError> = None;
+        content
+            .replace_all(&self.regex, |capture: &Captures<'_>, span| {
+                let args = capture
+                    .get(1)
+                    .expect("Generated regex should have a capture group.")
+                    .as_str();
 
-            let validate_result = Self::validate(args.to_owned(), &self.params);
+                let validate_result = Self::validate(args.to_owned(), &self.params);
 
-            if let Err(e) = validate_result {
-                validation_error = Some(e);
-                return String::new();
-            }
+                if let Err(e) = validate_result {
+                    validation_error.get_or_insert(e.at(span));
+                    return String::new();
+                }
 
-            let input_params = validate_result.unwrap();
+                let input_params = validate_result.unwrap();
 
-            let uuids = input_params
-                .iter()
-                .map(|_| Uuid::new_v4().to_string())
-                .collect::<Vec<String>>();
+                let uuids = input_params
+                    .iter()
+                    .map(|_| Uuid::new_v4().to_string())
+                    .collect::<Vec<String>>();
 
-            let pair = self
-                .params
-                .iter()
-                .zip(input_params.into_iter())
-                .zip(uuids)
-                .map(|((param, input), id)| (param.clone(), input, format!("__UUID_{}_UUID__", id)))
-                .collect::<Vec<(String, String, String)>>();
+                let pair = self
+                    .params
+                    .iter()
+                    .zip(input_params.into_iter())
+                    .zip(uuids)
+                    .map(|((param, input), id)| {
+                        (param.clone(), input, format!("__UUID_{}_UUID__", id))
+                    })
+                    .collect::<Vec<(String, String, String)>>();
 
-            let mut content_new = self.content.clone();
+                let mut content_new = self.content.clone();
 
-            for (param, _, id) in pair.iter() {
-                content_new = content_new.replace(&format!("*{}", param), id);
-            }
-            for (_, input, id) in pair.iter() {
-                content_new = content_new.replace(id, input);
-            }
-            content_new
-        });
+                for (param, _, id) in pair.iter() {
+                    content_new = content_new.replace(&format!("*{}", param), id);
+                }
+                for (_, input, id) in pair.iter() {
+                    content_new = content_new.replace(id, input);
+                }
+                content_new
+            })
+            .map_err(|e| ArcError::function(format!("Regex error: {}", e)))?;
 
         if let Some(e) = validation_error {
             return Err(e);
         }
-        *content = result.to_string();
         Ok(())
     }
 }
 
 impl Function for InlineFunction {
-    fn new(name: String, params: String, content: String) -> Result<Self, String> {
+    fn new(
+        name: String,
+        params: String,
+        content: String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ArcError> {
         let params = params.trim();
         if params[1..].trim().is_empty() {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid function argument / name: {}, cannot be empty",
                 params
-            ));
+            )));
         }
         if name.trim().is_empty() {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid function argument / name: {}, cannot be empty",
                 name
-            ));
+            )));
         }
         let name = if !name.trim().starts_with("$") {
-            crate::warn!(
-                "Runtime Warning: function name should start with `$`, got {}",
-                name
+            diagnostics.warn(
+                ArcError::function(format!("function name should start with `$`, got {}", name))
+                    .with_help("`$` is automatically added for inline functions"),
             );
-            crate::warn!("Runtime Warning: `$` is automatically added for inline function");
             format!("${}", name.trim())
         } else {
             String::from(name.trim())
@@ -126,46 +149,55 @@ impl Function for InlineFunction {
                 .expect("Generated regex should be valid."),
         })
     }
-    fn invoke(&self, content: &mut String) -> Result<(), String> {
-        let mut validation_error: Option<String> = None;
-        let result = self.regex.replace_all(content, |capture: &Captures<'_>| {
-            let args = capture
-                .get(1)
-                .expect("Generated regex should have a capture group.")
-                .as_str();
+    fn invoke(&self, content: &mut SourceText) -> Result<(), ArcError> {
+        let mut validation_error: Option<ArcError> = None;
+        content
+            .replace_all(&self.regex, |capture: &Captures<'_>, span| {
+                let args = capture
+                    .get(1)
+                    .expect("Generated regex should have a capture group.")
+                    .as_str();
 
-            if let Err(e) = Self::validate(args.to_owned()) {
-                validation_error = Some(e);
-                return String::new();
-            }
+                if let Err(e) = Self::validate(args.to_owned()) {
+                    validation_error.get_or_insert(e.at(span));
+                    return String::new();
+                }
 
-            let replacement = self.content.replace(&format!("*{}", self.params), args);
-            replacement
-        });
+                let replacement = self.content.replace(&format!("*{}", self.params), args);
+                replacement
+            })
+            .map_err(|e| ArcError::function(format!("Regex error: {}", e)))?;
 
         if let Some(e) = validation_error {
             return Err(e);
         }
-        *content = result.to_string();
         Ok(())
     }
 }
 
 impl Function for MultiLineFunction {
-    fn new(name: String, params: String, content: String) -> Result<Self, String> {
+    fn new(
+        name: String,
+        params: String,
+        content: String,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ArcError> {
         let params = if params.trim().is_empty() {
             None
         } else {
             Some(params.trim().to_string())
         };
         if name.trim().is_empty() {
-            return Err(format!("Invalid function name: {}, cannot be empty", name));
+            return Err(ArcError::function(format!(
+                "Invalid function name: {}, cannot be empty",
+                name
+            )));
         }
         if !name.trim().starts_with("$") {
-            crate::warn!(
-                "Runtime Warning: function name should start with `$`, got {}",
+            diagnostics.warn(ArcError::function(format!(
+                "function name should start with `$`, got {}",
                 name
-            );
+            )));
         }
         Ok(MultiLineFunction {
             params,
@@ -175,44 +207,47 @@ impl Function for MultiLineFunction {
         })
     }
 
-    fn invoke(&self, content: &mut String) -> Result<(), String> {
-        let result = self.regex.replace_all(content, |capture: &Captures<'_>| {
-            let args = capture
-                .get(1)
-                .expect("Generated regex should have a capture group.")
-                .as_str();
+    fn invoke(&self, content: &mut SourceText) -> Result<(), ArcError> {
+        content
+            .replace_all(&self.regex, |capture: &Captures<'_>, _| {
+                let args = capture
+                    .get(1)
+                    .expect("Generated regex should have a capture group.")
+                    .as_str();
 
-            if let Some(params) = &self.params {
-                self.content.replace(params, args)
-            } else {
-                self.content.clone()
-            }
-        });
-        *content = result.to_string();
-        Ok(())
+                if let Some(params) = &self.params {
+                    self.content.replace(params, args)
+                } else {
+                    self.content.clone()
+                }
+            })
+            .map_err(|e| ArcError::function(format!("Regex error: {}", e)))
     }
 }
 
 impl InlineFunction {
-    fn validate(input_params: String) -> Result<(), String> {
+    fn validate(input_params: String) -> Result<(), ArcError> {
         if input_params.replace("%", "").trim().is_empty() {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid inline function arguments: {}",
                 input_params
-            ));
+            )));
         }
         Ok(())
     }
 }
 
 impl FullFunction {
-    fn parse_args(args: String) -> Result<Vec<String>, String> {
+    fn parse_args(args: String) -> Result<Vec<String>, ArcError> {
         let args = args.trim();
         if args.is_empty() {
             return Ok(Vec::new());
         }
         if !(args.starts_with("*")) {
-            return Err(format!("Invalid function arguments: {}", args));
+            return Err(
+                ArcError::function(format!("Invalid function arguments: {}", args))
+                    .with_help("function parameters are written as `*name`, separated by spaces"),
+            );
         }
         let params: Vec<String> = args[1..]
             .split(" *")
@@ -221,7 +256,7 @@ impl FullFunction {
 
         Ok(params)
     }
-    fn validate(input_params: String, params: &Vec<String>) -> Result<Vec<String>, String> {
+    fn validate(input_params: String, params: &Vec<String>) -> Result<Vec<String>, ArcError> {
         let params = params
             .into_iter()
             .filter(|p| !p.is_empty())
@@ -232,20 +267,21 @@ impl FullFunction {
         }
 
         if params.len() == 0 && !input_params.trim().is_empty() {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid function arguments: {}, expected no arguments",
                 input_params
-            ));
+            )));
         }
         if !input_params.starts_with("%") && params.len() > 0 {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid function arguments: expect {},but got {}",
                 params.len(),
                 input_params
                     .split("%")
                     .filter(|s| !s.trim().is_empty())
                     .count()
-            ));
+            ))
+            .with_help("every argument must start with `%`, e.g. `$name(%first %second)`"));
         }
 
         let input_parameters = input_params[1..]
@@ -254,12 +290,13 @@ impl FullFunction {
             .collect::<Vec<String>>();
 
         if input_parameters.len() != params.len() {
-            return Err(format!(
+            return Err(ArcError::function(format!(
                 "Invalid function arguments: {}, expected {} arguments, got {}",
                 input_params,
                 params.len(),
                 input_parameters.len()
-            ));
+            ))
+            .with_help("arguments are separated by ` %`, e.g. `$name(%first %second)`"));
         }
         Ok(input_parameters)
     }
@@ -1,68 +1,49 @@
-use super::patterns::RegexPattern;
+use super::patterns::{FULL_PATTERNS, INLINE_PATTERNS, Lexeme, RegexPattern, lex_container};
+use super::span::{SourceText, Span};
 use super::token::{Token, TokenKind};
 use super::traits::LexerTrait;
 use crate::funcs::process::FunctionProcessor;
-use crate::utilities::constants::{COMMENT_REGEX, CRLF_REGEX};
-use fancy_regex::Regex;
+use crate::utilities::constants::STD_LIB_DIRECTORY;
+use crate::utilities::deadline::Deadline;
+use crate::utilities::diagnostics::Diagnostics;
+use crate::utilities::error::ArcError;
+use crate::utilities::regexes;
+use std::path::PathBuf;
 
 pub struct Lexer {
     pub token: Vec<Token>,
-    source: String,
+    source: SourceText,
     position: usize,
+    diagnostics: Diagnostics,
+    deadline: Deadline,
+    stdlib: PathBuf,
+    safe_mode: bool,
+    preprocess: bool,
 }
 
 impl LexerTrait for Lexer {
     fn new(source: String) -> Self {
         Self {
             token: Vec::new(),
-            source,
+            source: SourceText::new(source),
             position: 0,
+            diagnostics: Diagnostics::new(),
+            deadline: Deadline::none(),
+            stdlib: PathBuf::from(STD_LIB_DIRECTORY),
+            safe_mode: false,
+            preprocess: true,
         }
     }
-    fn tokenize(mut self) -> Result<Vec<Token>, String> {
-        self.preprocess()?;
-        let patterns_start_of_line = RegexPattern::<Lexer>::get_full_regex();
-        let patterns_not_start_of_line = RegexPattern::<Lexer>::get_inline_regex();
-        let mut previous_token_is_eol = true;
-
-        'outer: while !self.at_eof() {
-            let reminder = self.reminder();
-
-            let patterns_clone: &[RegexPattern<Lexer>] = if previous_token_is_eol {
-                &patterns_start_of_line
-            } else {
-                &patterns_not_start_of_line
-            };
-
-            for pattern in patterns_clone {
-                if let Ok(Some(loc)) = pattern.regex.find(&reminder) {
-                    if loc.start() == 0 {
-                        let matched_str = &pattern.regex;
-                        if matched_str.as_str().len() == 0 {
-                            return Err("Lexer: tokenize: zero length match".to_string());
-                        };
-                        (pattern.handler)(&mut self, matched_str)?;
-                        previous_token_is_eol = self
-                            .token
-                            .last()
-                            .unwrap_or(&Token::new(TokenKind::EndOfLine, None))
-                            .kind
-                            == TokenKind::EndOfLine;
-                        continue 'outer;
-                    }
-                }
-            }
-            return Err(format!(
-                "Lexer: No pattern matched at position {}, reminder: {}",
-                self.position, reminder
-            ));
+    fn tokenize(self) -> Result<Vec<Token>, ArcError> {
+        let (tokens, diagnostics) = self.tokenize_with_diagnostics()?;
+        match diagnostics.first_error() {
+            Some(error) => Err(error),
+            None => Ok(tokens),
         }
-        self.push(Token::new(TokenKind::EOF, None));
-        Ok(self.token)
     }
 
     fn reminder(&self) -> &str {
-        &self.source[self.position..]
+        &self.source.text[self.position..]
     }
     fn advance_n(&mut self, n: usize) {
         self.position += n;
@@ -70,22 +51,161 @@ impl LexerTrait for Lexer {
     fn push(&mut self, token: Token) {
         self.token.push(token);
     }
+    fn span(&self, length: usize) -> Span {
+        self.source.span(self.position, self.position + length)
+    }
 }
 
 impl Lexer {
-    fn preprocess(&mut self) -> Result<(), String> {
-        let mut source = self.source.replace("\r\n", "\n").replace("\r", "\n");
-        let crlf_regex = Regex::new(CRLF_REGEX).unwrap();
-        source = crlf_regex.replace_all(&source, "").to_string();
-        let comment_regex = Regex::new(COMMENT_REGEX).unwrap();
-        source = comment_regex.replace_all(&source, "").to_string();
+    /// Stops tokenizing, and expanding functions, with a timeout error once `deadline`
+    /// passes.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Directory `@include <std/...>` is resolved against while expanding functions.
+    pub fn with_stdlib(mut self, stdlib: PathBuf) -> Self {
+        self.stdlib = stdlib;
+        self
+    }
+
+    /// Refuses `@include` while expanding functions, see `FunctionProcessor::with_safe_mode`.
+    pub fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Tokenizes source cut out of a document that was already preprocessed, such as a
+    /// callout body, as it is. Spans still point into the original file.
+    pub fn nested(source: SourceText) -> Self {
+        Self {
+            source,
+            preprocess: false,
+            ..Self::new(String::new())
+        }
+    }
+
+    /// Tokenizes the source and hands back the warnings raised while preprocessing it.
+    /// Text that cannot be tokenized is recorded as an error and skipped up to the end
+    /// of its line, leaving an `Error` token in its place.
+    pub fn tokenize_with_diagnostics(mut self) -> Result<(Vec<Token>, Diagnostics), ArcError> {
+        if self.preprocess {
+            self.preprocess()?;
+        }
+        let mut previous_token_is_eol = true;
+
+        while !self.at_eof() {
+            self.deadline.check()?;
+            let patterns: &[RegexPattern] = if previous_token_is_eol {
+                &*FULL_PATTERNS
+            } else {
+                &*INLINE_PATTERNS
+            };
+
+            let container = previous_token_is_eol
+                .then(|| lex_container(self.reminder()))
+                .flatten();
+            let lexed = match container {
+                Some(lexeme) => Ok(Some(lexeme)),
+                None => RegexPattern::lex(patterns, self.reminder()),
+            };
+            match lexed {
+                Ok(Some(lexeme)) => {
+                    let body = self.container_body(&lexeme);
+                    self.accept(lexeme);
+                    if let Some(token) = self.token.last_mut().filter(|_| body.is_some()) {
+                        token.body = body;
+                    }
+                    previous_token_is_eol = self
+                        .token
+                        .last()
+                        .is_none_or(|token| token.kind == TokenKind::EndOfLine);
+                }
+                Ok(None) => {
+                    let error = ArcError::lexer(format!(
+                        "No pattern matched at position {}",
+                        self.position
+                    ))
+                    .with_help(
+                        "this is likely a bug in arc, please report the input that caused it",
+                    );
+                    self.recover(error);
+                    previous_token_is_eol = false;
+                }
+                Err(error) => {
+                    self.recover(error);
+                    previous_token_is_eol = false;
+                }
+            }
+        }
+        let span = self.span(0);
+        self.push(Token::new(TokenKind::Eof, None, span));
+        Ok((self.token, self.diagnostics))
+    }
+
+    fn recover(&mut self, error: ArcError) {
+        let length = self.reminder().find('\n').unwrap_or(self.reminder().len());
+        let length = length.max(self.reminder().chars().next().map_or(0, char::len_utf8));
+        let span = self.span(length);
+        let error = error.at(span);
+        self.push(Token::new(
+            TokenKind::Error,
+            Some(error.message.clone()),
+            span,
+        ));
+        self.diagnostics.error(error);
+        self.advance_n(length);
+    }
+
+    /// The body of the table, callout or details section `lexeme` starts at the current
+    /// position: the rows of a table, everything after the header line of the others.
+    fn container_body(&self, lexeme: &Lexeme) -> Option<SourceText> {
+        let value = lexeme.value.as_deref()?;
+        let (start, end) = match lexeme.kind {
+            Some(TokenKind::Table) => {
+                let end = self.position + self.reminder()[..lexeme.length].rfind("\n---")?;
+                (end - value.len(), end)
+            }
+            Some(TokenKind::Callout | TokenKind::Details) => {
+                let end = self.position + value.len();
+                let start = value
+                    .find('\n')
+                    .map_or(end, |header| self.position + header + 1);
+                (start, end)
+            }
+            _ => return None,
+        };
+        Some(self.source.slice(start, end))
+    }
+
+    fn preprocess(&mut self) -> Result<(), ArcError> {
+        let mut source = self.source.clone();
+        source.replace("\r\n", "\n");
+        source.replace("\r", "\n");
+        // info: inline code is taken out so comments and functions leave it alone
+        let inline_code = source
+            .protect(&regexes::INLINE_CODE)
+            .map_err(|e| ArcError::lexer(format!("Regex error: {}", e)))?;
+        source
+            .replace_all(&regexes::CRLF, |_, _| String::new())
+            .map_err(|e| ArcError::lexer(format!("Regex error: {}", e)))?;
+        source
+            .replace_all(&regexes::COMMENT, |_, _| String::new())
+            .map_err(|e| ArcError::lexer(format!("Regex error: {}", e)))?;
 
-        let fp = FunctionProcessor::new(source);
-        self.source = fp.process()?;
+        let fp = FunctionProcessor::new(source)
+            .with_deadline(self.deadline)
+            .with_stdlib(self.stdlib.clone())
+            .with_safe_mode(self.safe_mode);
+        let (mut source, diagnostics) = fp.process()?;
+        source.restore(&inline_code);
+        self.source = source;
+        self.diagnostics.extend(diagnostics);
         Ok(())
     }
 
     fn at_eof(&self) -> bool {
-        self.position >= self.source.len()
+        self.position >= self.source.text.len()
     }
 }
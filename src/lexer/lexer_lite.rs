@@ -1,11 +1,16 @@
-use super::patterns::RegexPattern;
+use super::patterns::{INLINE_PATTERNS, RegexPattern};
+use super::span::Span;
 use super::token::{Token, TokenKind};
 use super::traits::LexerTrait;
+use crate::utilities::error::ArcError;
 
+/// Inline-only lexer used for nested content such as table cells. Every token it
+/// produces carries the span of the enclosing construct.
 pub struct LexerLite {
     pub token: Vec<Token>,
     source: String,
     position: usize,
+    base: Span,
 }
 
 impl LexerTrait for LexerLite {
@@ -14,33 +19,24 @@ impl LexerTrait for LexerLite {
             token: Vec::new(),
             source,
             position: 0,
+            base: Span::default(),
         }
     }
 
-    fn tokenize(mut self) -> Result<Vec<Token>, String> {
-        let patterns = RegexPattern::<LexerLite>::get_inline_regex();
-
-        'outer: while !self.at_eof() {
-            let reminder = self.reminder();
-
-            for pattern in &patterns {
-                if let Ok(Some(loc)) = pattern.regex.find(&reminder) {
-                    if loc.start() == 0 {
-                        let matched_str = &pattern.regex;
-                        if matched_str.as_str().len() == 0 {
-                            return Err("Lexer: tokenize: zero length match".to_string());
-                        }
-                        (pattern.handler)(&mut self, matched_str)?;
-                        continue 'outer;
-                    }
-                }
-            }
-            return Err(format!(
-                "Lexer: No pattern matched at position {}, reminder: {}",
-                self.position, reminder
-            ));
+    fn tokenize(mut self) -> Result<Vec<Token>, ArcError> {
+        while !self.at_eof() {
+            let lexeme = RegexPattern::lex(&*INLINE_PATTERNS, self.reminder())
+                .map_err(|e| e.at(self.span(1)))?
+                .ok_or_else(|| {
+                    ArcError::lexer(format!("No pattern matched at position {}", self.position))
+                        .at(self.span(1))
+                        .with_help(
+                            "this is likely a bug in arc, please report the input that caused it",
+                        )
+                })?;
+            self.accept(lexeme);
         }
-        self.push(Token::new(TokenKind::EOF, None));
+        self.push(Token::new(TokenKind::Eof, None, self.base));
         Ok(self.token)
     }
     fn reminder(&self) -> &str {
@@ -52,9 +48,17 @@ impl LexerTrait for LexerLite {
     fn push(&mut self, token: Token) {
         self.token.push(token);
     }
+    fn span(&self, _length: usize) -> Span {
+        self.base
+    }
 }
 
 impl LexerLite {
+    pub fn with_base(mut self, base: Span) -> Self {
+        self.base = base;
+        self
+    }
+
     fn at_eof(&self) -> bool {
         self.position >= self.source.len()
     }
@@ -1,5 +1,6 @@
 pub mod lexer;
 pub mod lexer_lite;
 mod patterns;
+pub mod span;
 pub mod token;
 pub mod traits;
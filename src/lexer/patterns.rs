@@ -1,315 +1,404 @@
-use super::lexer::Lexer;
-use super::token::{Token, TokenKind};
-use super::traits::LexerTrait;
+use super::token::TokenKind;
 use crate::utilities::constants::*;
-use fancy_regex::Regex;
+use crate::utilities::error::ArcError;
+use fancy_regex::{Captures, Regex};
+use std::sync::LazyLock;
 
-pub struct RegexPattern<L: LexerTrait> {
+/// What a pattern produced at the current position: the token to emit, if any,
+/// and how many bytes of input it consumed.
+pub struct Lexeme {
+    pub kind: Option<TokenKind>,
+    pub value: Option<String>,
+    pub length: usize,
+}
+
+pub type Handler = Box<dyn Fn(&Captures<'_>) -> Lexeme + Send + Sync>;
+
+/// Patterns tried at the start of a line, compiled once per process.
+pub static FULL_PATTERNS: LazyLock<[RegexPattern; 32]> =
+    LazyLock::new(RegexPattern::get_full_regex);
+
+/// Patterns tried inside a line, and by `LexerLite` for nested content.
+pub static INLINE_PATTERNS: LazyLock<[RegexPattern; 18]> =
+    LazyLock::new(RegexPattern::get_inline_regex);
+
+/// Matches a callout or details section at the start of `reminder`. A regex cannot
+/// count fences, so the body is scanned line by line: each line that opens a fenced
+/// container needs a `---` of its own before one closes the section, which lets
+/// sections nest to any depth. The value is the header line and the body, without
+/// the closing `---`; an unclosed section is not matched.
+pub fn lex_container(reminder: &str) -> Option<Lexeme> {
+    let header = reminder.split('\n').next()?;
+    let kind = match container_kind(header)? {
+        "details" => TokenKind::Details,
+        "note" | "warning" | "tip" | "danger" => TokenKind::Callout,
+        _ => return None,
+    };
+    let mut depth = 1;
+    let mut end = header.len();
+    while end < reminder.len() {
+        let start = end + 1;
+        end = reminder[start..]
+            .find('\n')
+            .map_or(reminder.len(), |length| start + length);
+        let line = &reminder[start..end];
+        if container_kind(line).is_some() {
+            depth += 1;
+        } else if line
+            .strip_prefix("---")
+            .is_some_and(|rest| rest.trim_matches([' ', '\t']).is_empty())
+        {
+            depth -= 1;
+            if depth == 0 {
+                return Some(Lexeme {
+                    kind: Some(kind),
+                    value: Some(reminder[..start - 1].to_string()),
+                    length: end,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// The kind of a `--- kind!` fence line, such as `note` or `table`.
+fn container_kind(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("---")?.trim_start_matches([' ', '\t']);
+    let length = rest.find(|c: char| !c.is_ascii_lowercase())?;
+    (length > 0 && rest[length..].starts_with('!')).then(|| &rest[..length])
+}
+
+/// A token pattern anchored at the current lexer position, so a failed match costs
+/// no more than the text it looked at instead of a scan of the rest of the file.
+pub struct RegexPattern {
     pub regex: Regex,
-    pub handler: Box<dyn Fn(&mut L, &Regex) -> Result<(), String>>,
+    pub handler: Handler,
 }
 
-impl<L: LexerTrait> RegexPattern<L> {
-    pub fn new(regex: Regex, handler: Box<dyn Fn(&mut L, &Regex) -> Result<(), String>>) -> Self {
-        Self { regex, handler }
+impl RegexPattern {
+    pub fn new(pattern: &str, handler: Handler) -> Self {
+        Self {
+            regex: Regex::new(&format!(r"\A(?:{})", pattern))
+                .expect("Hard coded regex should be valid."),
+            handler,
+        }
     }
 
-    pub fn non_capture_handler(
-        kind: TokenKind,
-    ) -> Box<dyn Fn(&mut L, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut L, regex: &Regex| {
-            let reminder = lexer.reminder();
-            let matched = regex
-                .find(reminder)
-                .map_err(|e| format!("Lexer: non_capture_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let length = matched.range().len();
-            if length == 0 {
-                return Err(String::from(
-                    "Lexer: non_capture_handler: zero length match",
-                ));
+    /// Tries `patterns` in order at the start of `reminder` and returns what the first
+    /// matching one produced, or `None` if no pattern matches there.
+    pub fn lex(patterns: &[RegexPattern], reminder: &str) -> Result<Option<Lexeme>, ArcError> {
+        for pattern in patterns {
+            let Ok(Some(captures)) = pattern.regex.captures(reminder) else {
+                continue;
+            };
+            let lexeme = (pattern.handler)(&captures);
+            if lexeme.length == 0 {
+                return Err(ArcError::lexer(format!(
+                    "Zero length match for pattern {}",
+                    pattern.regex.as_str()
+                )));
             }
-            lexer.push(Token::new(kind.clone(), None));
-            lexer.advance_n(length);
-            Ok(())
+            return Ok(Some(lexeme));
+        }
+        Ok(None)
+    }
+
+    fn length(captures: &Captures<'_>) -> usize {
+        captures
+            .get(0)
+            .expect("Capture group 0 should always exist.")
+            .as_str()
+            .len()
+    }
+
+    fn group<'a>(captures: &'a Captures<'_>, index: usize) -> &'a str {
+        captures
+            .get(index)
+            .expect("Lexer: hard coded regex pattern should have a capture group.")
+            .as_str()
+    }
+
+    pub fn non_capture_handler(kind: TokenKind) -> Handler {
+        Box::new(move |captures| Lexeme {
+            kind: Some(kind.clone()),
+            value: None,
+            length: Self::length(captures),
         })
     }
 
-    pub fn capture_handler(kind: TokenKind) -> Box<dyn Fn(&mut L, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut L, regex: &Regex| {
-            let reminder = lexer.reminder();
-            let matched = regex
-                .find(reminder)
-                .map_err(|e| format!("Lexer: capture_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let length = matched.range().len();
-            if length == 0 {
-                return Err(String::from("Lexer: capture_handler: zero length match"));
-            }
-            let matched = matched.as_str();
-            let capture = regex
-                .captures(matched)
-                .map_err(|e| format!("Lexer: capture_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.")
-                .get(1)
-                .expect("Lexer: hard coded regex pattern should have a capture group.")
-                .as_str();
-            lexer.push(Token::new(kind.clone(), Some(capture.to_string())));
-            lexer.advance_n(length);
-            Ok(())
+    pub fn capture_handler(kind: TokenKind) -> Handler {
+        Box::new(move |captures| Lexeme {
+            kind: Some(kind.clone()),
+            value: Some(Self::group(captures, 1).to_string()),
+            length: Self::length(captures),
         })
     }
 
-    pub fn skip_handler() -> Box<dyn Fn(&mut Lexer, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut Lexer, regex: &Regex| {
-            let matched = regex
-                .find(lexer.reminder())
-                .map_err(|e| format!("Lexer: skip_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            if matched.range().len() == 0 {
-                return Err(String::from("Lexer: skip_handler: zero length match"));
-            }
-            lexer.advance_n(matched.range().len());
-            Ok(())
+    /// Keeps the marker as the value, so one that cannot style anything is shown as
+    /// the text it was written as.
+    pub fn marker_handler(kind: TokenKind) -> Handler {
+        Box::new(move |captures| Lexeme {
+            kind: Some(kind.clone()),
+            value: Some(Self::group(captures, 0).to_string()),
+            length: Self::length(captures),
         })
     }
 
-    pub fn definition_handler(
-        delimiter: String,
-    ) -> Box<dyn Fn(&mut Lexer, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut Lexer, regex: &Regex| {
-            let reminder = lexer.reminder();
-            let matched = regex
-                .find(reminder)
-                .map_err(|e| format!("Lexer: definition_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let length = matched.range().len();
-            if length == 0 {
-                return Err(String::from("Lexer: definition_handler: zero length match"));
-            }
-            let matched = matched.as_str();
-            let captures = regex
-                .captures(matched)
-                .map_err(|e| format!("Lexer: definition_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let words = captures
-                .get(1)
-                .expect("Lexer: hard coded definition pattern should have a capture group.")
-                .as_str();
-            let expression = captures
-                .get(2)
-                .expect(
-                    "Lexer: hard coded definition pattern should have the second capture group.",
-                )
-                .as_str();
-            lexer.push(Token::new(
-                TokenKind::Definition,
-                Some(format!("{}{}{}", words, delimiter, expression)),
-            ));
-            lexer.advance_n(length);
-            Ok(())
+    pub fn skip_handler() -> Handler {
+        Box::new(|captures| Lexeme {
+            kind: None,
+            value: None,
+            length: Self::length(captures),
         })
     }
 
-    pub fn string_handler() -> Box<dyn Fn(&mut L, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut L, regex: &Regex| {
-            let reminder = lexer.reminder();
-            let match_result = regex
-                .find(reminder)
-                .map_err(|e| format!("Lexer: string_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let matched_slice = match_result.as_str();
-            let advance_len = matched_slice.len();
-            if advance_len == 0 {
-                return Err(String::from("Lexer: string_handler: zero length match"));
-            }
-            lexer.push(Token::new(
-                TokenKind::String,
-                Some(matched_slice.to_string()),
-            ));
-            lexer.advance_n(advance_len);
-            Ok(())
+    pub fn definition_handler(delimiter: String) -> Handler {
+        Box::new(move |captures| Lexeme {
+            kind: Some(TokenKind::Definition),
+            value: Some(format!(
+                "{}{}{}",
+                Self::group(captures, 1),
+                delimiter,
+                Self::group(captures, 2)
+            )),
+            length: Self::length(captures),
         })
     }
 
-    pub fn code_handler() -> Box<dyn Fn(&mut Lexer, &Regex) -> Result<(), String>> {
-        Box::new(move |lexer: &mut Lexer, regex: &Regex| {
-            let reminder = lexer.reminder();
-            let matched = regex
-                .find(reminder)
-                .map_err(|e| format!("Lexer: code_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let length = matched.range().len();
-            if length == 0 {
-                return Err(String::from("Lexer: code_handler: zero length match"));
+    pub fn string_handler() -> Handler {
+        Box::new(|captures| Lexeme {
+            kind: Some(TokenKind::String),
+            value: Some(Self::group(captures, 0).to_string()),
+            length: Self::length(captures),
+        })
+    }
+
+    pub fn code_handler() -> Handler {
+        Box::new(|captures| Lexeme {
+            kind: Some(TokenKind::CodeBlock),
+            value: Some(format!(
+                "{}{}{}",
+                Self::group(captures, 1),
+                "\n",
+                Self::group(captures, 2)
+            )),
+            length: Self::length(captures),
+        })
+    }
+
+    /// The value is the source, size, alt text and caption on separate lines, with
+    /// an empty line for a missing size or caption.
+    pub fn image_handler() -> Handler {
+        Box::new(|captures| Lexeme {
+            kind: Some(TokenKind::Image),
+            value: Some(
+                (1..=4)
+                    .map(|index| captures.get(index).map_or("", |m| m.as_str()))
+                    .collect::<Vec<&str>>()
+                    .join("\n"),
+            ),
+            length: Self::length(captures),
+        })
+    }
+
+    /// The value is the target, then the title and `>` for a new tab link on their own
+    /// lines; trailing empty lines are left out, so a plain link is just its target.
+    pub fn link_handler() -> Handler {
+        Box::new(|captures| {
+            let new_tab = Self::group(captures, 0).starts_with("&>");
+            let parts = [
+                Self::group(captures, 1),
+                captures.get(2).map_or("", |m| m.as_str()),
+                if new_tab { ">" } else { "" },
+            ];
+            let used = parts.iter().rposition(|part| !part.is_empty()).unwrap_or(0);
+            Lexeme {
+                kind: Some(TokenKind::Link),
+                value: Some(parts[..=used].join("\n")),
+                length: Self::length(captures),
             }
-            let matched = matched.as_str();
-            let captures = regex
-                .captures(matched)
-                .map_err(|e| format!("Lexer: code_handler receive a regex error: {}", e))?
-                .expect("Lexer: passed content should contain valid structure.");
-            let language = captures
-                .get(1)
-                .expect("Lexer: hard coded code block pattern should have a capture group.")
-                .as_str();
-            let content = captures
-                .get(2)
-                .expect(
-                    "Lexer: hard coded code block pattern should have the second capture group.",
-                )
-                .as_str();
-            lexer.push(Token::new(
-                TokenKind::CodeBlock,
-                Some(format!("{}{}{}", language, "\n", content)),
-            ));
-            lexer.advance_n(length);
-            Ok(())
         })
     }
 
-    pub fn get_full_regex() -> [RegexPattern<Lexer>; 22] {
+    fn get_full_regex() -> [RegexPattern; 32] {
         [
             RegexPattern::new(
-                Regex::new(NEWLINE_REGEX).expect("Hard coded regex should be valid."),
+                NEWLINE_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::EndOfLine),
             ),
             RegexPattern::new(
-                Regex::new(TABLE_CONTAINER_REGEX).expect("Hard coded regex should be valid."),
+                TABLE_CONTAINER_REGEX,
                 RegexPattern::capture_handler(TokenKind::Table),
             ),
             RegexPattern::new(
-                Regex::new(HTML_CONTAINER_REGEX).expect("Hard coded regex should be valid."),
+                HTML_CONTAINER_REGEX,
                 RegexPattern::capture_handler(TokenKind::HTMLContainer),
             ),
             RegexPattern::new(
-                Regex::new(HORIZONTAL_LINE_REGEX).expect("Hard coded regex should be valid."),
+                HORIZONTAL_LINE_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::HorizontalLine),
             ),
             RegexPattern::new(
-                Regex::new(BLOCK_MATH_REGEX).expect("Hard coded regex should be valid."),
+                BLOCK_MATH_REGEX,
                 RegexPattern::capture_handler(TokenKind::BlockMath),
             ),
+            RegexPattern::new(CODE_BLOCK_REGEX, RegexPattern::code_handler()),
             RegexPattern::new(
-                Regex::new(CODE_BLOCK_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::<Lexer>::code_handler(),
+                INLINE_MATH_REGEX,
+                RegexPattern::capture_handler(TokenKind::InlineMath),
             ),
+            // info: before whitespace, so list markers keep their indentation; the
+            // value is the indentation followed by the marker
             RegexPattern::new(
-                Regex::new(INLINE_MATH_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::InlineMath),
+                ORDERED_LIST_REGEX,
+                RegexPattern::capture_handler(TokenKind::OrderedList),
+            ),
+            RegexPattern::new(
+                UNORDERED_LIST_REGEX,
+                RegexPattern::capture_handler(TokenKind::UnorderedList),
+            ),
+            RegexPattern::new(WHITESPACE_REGEX, RegexPattern::skip_handler()),
+            RegexPattern::new(
+                BLOCKQUOTE_REGEX,
+                RegexPattern::capture_handler(TokenKind::BlockQuote),
+            ),
+            RegexPattern::new(
+                INLINE_CODE_REGEX,
+                RegexPattern::capture_handler(TokenKind::InlineCode),
             ),
+            RegexPattern::new(IMAGE_REGEX, RegexPattern::image_handler()),
+            RegexPattern::new(LINK_REGEX, RegexPattern::link_handler()),
             RegexPattern::new(
-                Regex::new(WHITESPACE_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::<Lexer>::skip_handler(),
+                FOOTNOTE_DEFINITION_REGEX,
+                RegexPattern::capture_handler(TokenKind::FootnoteDefinition),
             ),
             RegexPattern::new(
-                Regex::new(LINK_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::Link),
+                FOOTNOTE_REFERENCE_REGEX,
+                RegexPattern::capture_handler(TokenKind::FootnoteReference),
             ),
             RegexPattern::new(
-                Regex::new(DEFINITION_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::<Lexer>::definition_handler(String::from("-@[]")),
+                DEFINITION_REGEX,
+                RegexPattern::definition_handler(String::from("-@[]")),
             ),
             RegexPattern::new(
-                Regex::new(CHARACTER_STYLE_REGEX).expect("Hard coded regex should be valid."),
+                CHARACTER_STYLE_REGEX,
                 RegexPattern::capture_handler(TokenKind::CharacterStyle),
             ),
             RegexPattern::new(
-                Regex::new(META_DATA_REGEX_LONG).expect("Hard coded regex should be valid."),
+                META_DATA_REGEX_LONG,
                 RegexPattern::capture_handler(TokenKind::MetaData),
             ),
             RegexPattern::new(
-                Regex::new(META_DATA_REGEX_SHORT).expect("Hard coded regex should be valid."),
+                META_DATA_REGEX_SHORT,
                 RegexPattern::capture_handler(TokenKind::MetaData),
             ),
             RegexPattern::new(
-                Regex::new(LITERAL_RIGHT_PARENTHESIS_REGEX)
-                    .expect("Hard coded regex should be valid."),
+                TOC_REGEX,
+                RegexPattern::capture_handler(TokenKind::TableOfContents),
+            ),
+            RegexPattern::new(
+                LITERAL_RIGHT_PARENTHESIS_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::LiteralRightParenthesis),
             ),
             RegexPattern::new(
-                Regex::new(BACKSLASH_LEFT_PARENTHESIS_INLINE_REGEX)
-                    .expect("Hard coded regex should be valid."),
+                BACKSLASH_LEFT_PARENTHESIS_INLINE_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::BackSlashLeftParenthesisInline),
             ),
+            RegexPattern::new(BOLD_REGEX, RegexPattern::capture_handler(TokenKind::Bold)),
             RegexPattern::new(
-                Regex::new(BOLD_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::Bold),
+                HEADING_REGEX,
+                RegexPattern::capture_handler(TokenKind::Heading),
             ),
             RegexPattern::new(
-                Regex::new(HEADING_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::Heading),
+                STRIKETHROUGH_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::Strikethrough),
             ),
             RegexPattern::new(
-                Regex::new(ORDERED_LIST_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::OrderedList),
+                UNDERLINE_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::Underline),
             ),
             RegexPattern::new(
-                Regex::new(UNORDERED_LIST_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::UnorderedList),
+                SUPERSCRIPT_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::Superscript),
             ),
             RegexPattern::new(
-                Regex::new(ITALIC_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::Italic),
+                SUBSCRIPT_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::Subscript),
             ),
             RegexPattern::new(
-                Regex::new(RIGHT_PARENTHESIS_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::RightParenthesis),
+                ITALIC_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::Italic),
             ),
             RegexPattern::new(
-                Regex::new(STRING_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::string_handler(),
+                RIGHT_PARENTHESIS_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::RightParenthesis),
             ),
+            RegexPattern::new(STRING_REGEX, RegexPattern::string_handler()),
         ]
     }
 
-    pub fn get_inline_regex() -> [RegexPattern<L>; 11] {
+    fn get_inline_regex() -> [RegexPattern; 18] {
         [
             RegexPattern::new(
-                Regex::new(NEWLINE_REGEX).expect("Hard coded regex should be valid."),
+                NEWLINE_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::EndOfLine),
             ),
             RegexPattern::new(
-                Regex::new(INLINE_MATH_REGEX).expect("Hard coded regex should be valid."),
+                INLINE_CODE_REGEX,
+                RegexPattern::capture_handler(TokenKind::InlineCode),
+            ),
+            RegexPattern::new(
+                INLINE_MATH_REGEX,
                 RegexPattern::capture_handler(TokenKind::InlineMath),
             ),
             RegexPattern::new(
-                Regex::new(BLOCK_MATH_REGEX).expect("Hard coded regex should be valid."),
+                BLOCK_MATH_REGEX,
                 RegexPattern::capture_handler(TokenKind::BlockMath),
             ),
+            RegexPattern::new(IMAGE_REGEX, RegexPattern::image_handler()),
+            RegexPattern::new(LINK_REGEX, RegexPattern::link_handler()),
             RegexPattern::new(
-                Regex::new(LINK_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::Link),
+                FOOTNOTE_REFERENCE_REGEX,
+                RegexPattern::capture_handler(TokenKind::FootnoteReference),
             ),
             RegexPattern::new(
-                Regex::new(CHARACTER_STYLE_REGEX).expect("Hard coded regex should be valid."),
+                CHARACTER_STYLE_REGEX,
                 RegexPattern::capture_handler(TokenKind::CharacterStyle),
             ),
             RegexPattern::new(
-                Regex::new(LITERAL_RIGHT_PARENTHESIS_REGEX)
-                    .expect("Hard coded regex should be valid."),
+                LITERAL_RIGHT_PARENTHESIS_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::LiteralRightParenthesis),
             ),
             RegexPattern::new(
-                Regex::new(BACKSLASH_LEFT_PARENTHESIS_INLINE_REGEX)
-                    .expect("Hard coded regex should be valid."),
+                BACKSLASH_LEFT_PARENTHESIS_INLINE_REGEX,
                 RegexPattern::non_capture_handler(TokenKind::BackSlashLeftParenthesisInline),
             ),
+            RegexPattern::new(BOLD_REGEX, RegexPattern::capture_handler(TokenKind::Bold)),
             RegexPattern::new(
-                Regex::new(BOLD_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::capture_handler(TokenKind::Bold),
+                STRIKETHROUGH_REGEX,
+                RegexPattern::marker_handler(TokenKind::Strikethrough),
             ),
             RegexPattern::new(
-                Regex::new(ITALIC_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::Italic),
+                UNDERLINE_REGEX,
+                RegexPattern::marker_handler(TokenKind::Underline),
             ),
             RegexPattern::new(
-                Regex::new(RIGHT_PARENTHESIS_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::non_capture_handler(TokenKind::RightParenthesis),
+                SUPERSCRIPT_REGEX,
+                RegexPattern::marker_handler(TokenKind::Superscript),
+            ),
+            RegexPattern::new(
+                SUBSCRIPT_REGEX,
+                RegexPattern::marker_handler(TokenKind::Subscript),
             ),
             RegexPattern::new(
-                Regex::new(STRING_REGEX).expect("Hard coded regex should be valid."),
-                RegexPattern::string_handler(),
+                ITALIC_REGEX,
+                RegexPattern::marker_handler(TokenKind::Italic),
+            ),
+            RegexPattern::new(
+                RIGHT_PARENTHESIS_REGEX,
+                RegexPattern::non_capture_handler(TokenKind::RightParenthesis),
             ),
+            RegexPattern::new(STRING_REGEX, RegexPattern::string_handler()),
         ]
     }
 }
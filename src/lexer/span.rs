@@ -0,0 +1,231 @@
+use fancy_regex::{Captures, Regex};
+use std::fmt;
+
+/// A location in the original source file: the byte range `start..end` plus the
+/// 1-based line and column of its first character.
+#[derive(PartialEq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// Joins two spans, keeping the start of `self` and the end of `other`.
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            end: self.end.max(other.end),
+            ..*self
+        }
+    }
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Span({}:{}, {}..{})",
+            self.line, self.column, self.start, self.end
+        )
+    }
+}
+
+// info: private use characters, which never appear in ordinary source
+const PROTECT_START: char = '\u{E000}';
+const PROTECT_END: char = '\u{E001}';
+
+/// Preprocessed source text that remembers, for every byte, the offset it came
+/// from in the original file. Replacements map all of their output bytes to the
+/// start of the text they replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceText {
+    pub text: String,
+    original: String,
+    origin: Vec<usize>,
+    line_starts: Vec<usize>,
+}
+
+impl SourceText {
+    pub fn new(text: String) -> Self {
+        let origin = (0..=text.len()).collect();
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            original: text.clone(),
+            text,
+            origin,
+            line_starts,
+        }
+    }
+
+    /// Maps the byte range `start..end` of the current text back to the original file.
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        let original_start = self.origin(start);
+        let original_end = if end > start {
+            (self.origin(end - 1) + 1).max(original_start)
+        } else {
+            original_start
+        };
+        let (line, column) = self.line_column(original_start);
+        Span::new(original_start, original_end, line, column)
+    }
+
+    /// The bytes `start..end` of the current text, still mapped back to the original
+    /// file, e.g. to lex a callout body on its own.
+    pub fn slice(&self, start: usize, end: usize) -> SourceText {
+        Self {
+            text: self.text[start..end].to_string(),
+            original: self.original.clone(),
+            origin: self.origin[start..=end].to_vec(),
+            line_starts: self.line_starts.clone(),
+        }
+    }
+
+    pub fn replace(&mut self, from: &str, to: &str) {
+        if !self.text.contains(from) {
+            return;
+        }
+        let mut text = String::with_capacity(self.text.len());
+        let mut origin = Vec::with_capacity(self.origin.len());
+        let mut last = 0;
+        for (index, _) in self.text.match_indices(from) {
+            self.copy_range(last, index, &mut text, &mut origin);
+            Self::push_replacement(to, self.origin[index], &mut text, &mut origin);
+            last = index + from.len();
+        }
+        self.copy_range(last, self.text.len(), &mut text, &mut origin);
+        self.commit(text, origin);
+    }
+
+    /// Replaces every match of `regex`. The replacer also receives the span of
+    /// the match in the original file.
+    pub fn replace_all<F>(&mut self, regex: &Regex, mut replacer: F) -> Result<(), String>
+    where
+        F: FnMut(&Captures<'_>, Span) -> String,
+    {
+        let mut text = String::with_capacity(self.text.len());
+        let mut origin = Vec::with_capacity(self.origin.len());
+        let mut last = 0;
+        for captures in regex.captures_iter(&self.text) {
+            let captures = captures.map_err(|e| e.to_string())?;
+            let matched = captures
+                .get(0)
+                .expect("Capture group 0 should always exist.");
+            self.copy_range(last, matched.start(), &mut text, &mut origin);
+            let replacement = replacer(&captures, self.span(matched.start(), matched.end()));
+            Self::push_replacement(
+                &replacement,
+                self.origin[matched.start()],
+                &mut text,
+                &mut origin,
+            );
+            last = matched.end();
+        }
+        self.copy_range(last, self.text.len(), &mut text, &mut origin);
+        self.commit(text, origin);
+        Ok(())
+    }
+
+    /// Swaps every match of `regex` for a placeholder, so later rewrites such as
+    /// function expansion cannot touch it. `restore` puts the matches back.
+    pub fn protect(&mut self, regex: &Regex) -> Result<Vec<(String, Vec<usize>)>, String> {
+        let mut protected = Vec::new();
+        let mut text = String::with_capacity(self.text.len());
+        let mut origin = Vec::with_capacity(self.origin.len());
+        let mut last = 0;
+        for matched in regex.find_iter(&self.text) {
+            let matched = matched.map_err(|e| e.to_string())?;
+            self.copy_range(last, matched.start(), &mut text, &mut origin);
+            Self::push_replacement(
+                &format!("{}{}{}", PROTECT_START, protected.len(), PROTECT_END),
+                self.origin[matched.start()],
+                &mut text,
+                &mut origin,
+            );
+            protected.push((
+                matched.as_str().to_string(),
+                self.origin[matched.start()..matched.end()].to_vec(),
+            ));
+            last = matched.end();
+        }
+        self.copy_range(last, self.text.len(), &mut text, &mut origin);
+        self.commit(text, origin);
+        Ok(protected)
+    }
+
+    /// Puts back what `protect` took out, with the offsets it had before. A
+    /// placeholder that was copied is restored at every copy.
+    pub fn restore(&mut self, protected: &[(String, Vec<usize>)]) {
+        let mut text = String::with_capacity(self.text.len());
+        let mut origin = Vec::with_capacity(self.origin.len());
+        let mut last = 0;
+        while let Some(start) = self.text[last..].find(PROTECT_START).map(|i| i + last) {
+            let digits = start + PROTECT_START.len_utf8();
+            let Some(end) = self.text[digits..].find(PROTECT_END).map(|i| i + digits) else {
+                break;
+            };
+            let Some((original, offsets)) = self.text[digits..end]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| protected.get(index))
+            else {
+                self.copy_range(last, digits, &mut text, &mut origin);
+                last = digits;
+                continue;
+            };
+            self.copy_range(last, start, &mut text, &mut origin);
+            text.push_str(original);
+            origin.extend_from_slice(offsets);
+            last = end + PROTECT_END.len_utf8();
+        }
+        self.copy_range(last, self.text.len(), &mut text, &mut origin);
+        self.commit(text, origin);
+    }
+
+    fn origin(&self, offset: usize) -> usize {
+        self.origin[offset.min(self.text.len())]
+    }
+
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self
+            .original
+            .get(line_start..offset)
+            .map_or(offset - line_start, |s| s.chars().count());
+        (line, column + 1)
+    }
+
+    fn copy_range(&self, start: usize, end: usize, text: &mut String, origin: &mut Vec<usize>) {
+        text.push_str(&self.text[start..end]);
+        origin.extend_from_slice(&self.origin[start..end]);
+    }
+
+    fn push_replacement(replacement: &str, at: usize, text: &mut String, origin: &mut Vec<usize>) {
+        text.push_str(replacement);
+        origin.extend(std::iter::repeat_n(at, replacement.len()));
+    }
+
+    fn commit(&mut self, text: String, mut origin: Vec<usize>) {
+        origin.push(self.origin(self.text.len()));
+        self.text = text;
+        self.origin = origin;
+    }
+}
+
+impl From<String> for SourceText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
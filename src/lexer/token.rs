@@ -1,36 +1,60 @@
+use super::span::{SourceText, Span};
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub value: Option<String>,
     pub kind: TokenKind,
+    pub span: Span,
+    /// The body of a callout or details section, as preprocessed text that still maps
+    /// back to the original file.
+    pub body: Option<SourceText>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenKind {
     EndOfLine,
-    EOF,
+    Eof,
     CharacterStyle,                 // %[{style syntax}] ==> %\[.*?\]
     MetaData,                       // <meta /> ==> <meta ([^\n]*) />
-    UnorderedList,                  // - ==> -\s?
-    OrderedList,                    // {number}. ==> ^\d+\.\s?
-    Italic,                         // ~ => ~
-    Bold,                           // **{any content} ** ==> \*\*[^*]*\*\*
-    Definition,                     // @[words] 'expression' ==> @\[.*?\] ?'.*?'
-    Heading,                        // #, ##, ###, #### ==> ^\#{1,4}
+    TableOfContents,                // <toc /> | <toc depth=2 />
+    UnorderedList,                  // - | - [ ] | - [x]
+    OrderedList,                    // {number}. | {letter}. | {roman}. | ({number})
+    BlockQuote,    // > | >> | > > | > -- ==> one `>` per level, `--` for attribution
+    Italic,        // ~ => ~
+    Strikethrough, // ~~ ==> ~~ ?
+    Underline,     // __ ==> __ ?
+    Superscript,   // ^^ ==> \^\^ ?
+    Subscript,     // ,, ==> ,, ?
+    Bold,          // **{any content} ** ==> \*\*[^*]*\*\*
+    Definition,    // @[words] 'expression' ==> @\[.*?\] ?'.*?'
+    Heading,       // #, ##, ..., ###### ==> ^\#{1,6}
     BackSlashLeftParenthesisInline, // \( ==> \\\(
-    RightParenthesis,               // ) ==> ) \)
-    LiteralRightParenthesis,        // \) ==> \\\)
-    String,                         // ((?:[^()\\*]|\*(?:[^*]|$))+)
-    Link, // &[url] ==> &\[https?:\/\/[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}(?:\/[^\s]*)*\]
+    RightParenthesis, // ) ==> ) \)
+    LiteralRightParenthesis, // \) ==> \\\)
+    String,        // ((?:[^()\\*]|\*(?:[^*]|$))+)
+    Image,         // ![src] (w, h) 'alt' 'caption' ==> the size and caption are optional
+    Link,          // &[target 'title'] | &>[target] ==> the title and new tab marker are optional
+    FootnoteReference, // [^label]
+    FootnoteDefinition, // [^label]: ==> at the start of a line, followed by the note
     Table,
     InlineMath,
     BlockMath,
     HorizontalLine,
     CodeBlock,
+    InlineCode, // `code` ==> `[^`\n]+`
     HTMLContainer,
+    Callout, // --- note! title ... --- ==> the header line, then the body
+    Details, // --- details! summary ... --- ==> the header line, then the body
+    Error,   // text the lexer could not tokenize, already reported
 }
 
 impl Token {
-    pub fn new(kind: TokenKind, value: Option<String>) -> Self {
-        Self { kind, value }
+    pub fn new(kind: TokenKind, value: Option<String>, span: Span) -> Self {
+        Self {
+            kind,
+            value,
+            span,
+            body: None,
+        }
     }
 }
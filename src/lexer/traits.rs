@@ -1,9 +1,22 @@
+use super::patterns::Lexeme;
+use super::span::Span;
 use super::token::Token;
+use crate::utilities::error::ArcError;
 
 pub trait LexerTrait {
     fn new(source: String) -> Self;
-    fn tokenize(self) -> Result<Vec<Token>, String>;
+    fn tokenize(self) -> Result<Vec<Token>, ArcError>;
     fn reminder(&self) -> &str;
     fn advance_n(&mut self, n: usize);
     fn push(&mut self, token: Token);
+    fn span(&self, length: usize) -> Span;
+
+    /// Emits the token a pattern produced, if any, and moves past the consumed input.
+    fn accept(&mut self, lexeme: Lexeme) {
+        if let Some(kind) = lexeme.kind {
+            let span = self.span(lexeme.length);
+            self.push(Token::new(kind, lexeme.value, span));
+        }
+        self.advance_n(lexeme.length);
+    }
 }
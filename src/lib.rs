@@ -0,0 +1,88 @@
+//! Arc compiles Accelerated Markup Language documents to HTML.
+//!
+//! ```no_run
+//! let options = arc::Options {
+//!     safe_mode: true,
+//!     ..arc::Options::default()
+//! };
+//! let html = arc::render("# Lecture notes\nHello, **world**!", &options)?;
+//! # Ok::<(), arc::ArcError>(())
+//! ```
+mod funcs;
+mod lexer;
+pub(crate) mod parse;
+pub(crate) mod utilities;
+
+mod test;
+
+use lexer::lexer::Lexer;
+use lexer::traits::LexerTrait as _;
+use parse::parse::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
+use utilities::deadline::Deadline;
+
+pub use parse::tree::{Document, TaskCount};
+pub use utilities::diagnostics::Diagnostics;
+pub use utilities::error::{ArcError, ErrorKind, Report, Severity};
+pub use utilities::style::Theme;
+
+// info: not part of the library's API, only shared with the `arc` binary
+#[doc(hidden)]
+pub use utilities::constants::STD_LIB_DIRECTORY;
+#[doc(hidden)]
+pub use utilities::lib::STD_LIB;
+#[doc(hidden)]
+pub use utilities::stdout::{arg_style, command_style, show_err, show_err_json, show_success};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// Stylesheet embedded in the rendered page.
+    pub theme: Theme,
+    /// Directory `@include <std/...>` is resolved against.
+    pub stdlib_path: PathBuf,
+    /// Compilation fails with an `ErrorKind::Timeout` error after this long; `None`
+    /// for no limit.
+    pub timeout: Option<Duration>,
+    /// Refuses `@include`, renders raw HTML blocks as text and escapes math, for
+    /// compiling untrusted input.
+    pub safe_mode: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            theme: Theme::Default,
+            stdlib_path: PathBuf::from(STD_LIB_DIRECTORY),
+            timeout: Some(Duration::from_secs(5)),
+            safe_mode: false,
+        }
+    }
+}
+
+/// Lexes and parses `src`. Errors the compiler recovered from are collected in
+/// `Document::diagnostics` together with the warnings; `Err` means compilation could
+/// not finish at all, e.g. on a timeout.
+pub fn compile(src: &str, options: &Options) -> Result<Document, ArcError> {
+    let deadline = Deadline::new(options.timeout);
+    let (tokens, diagnostics) = Lexer::new(src.to_string())
+        .with_deadline(deadline)
+        .with_stdlib(options.stdlib_path.clone())
+        .with_safe_mode(options.safe_mode)
+        .tokenize_with_diagnostics()?;
+    Parser::new(tokens)
+        .with_diagnostics(diagnostics)
+        .with_deadline(deadline)
+        .with_safe_mode(options.safe_mode)
+        .parse_with_recovery()
+}
+
+/// Compiles `src` to a standalone HTML page styled with `options.theme`. Fails on the
+/// first error; warnings are dropped, use [`compile`] to inspect them.
+pub fn render(src: &str, options: &Options) -> Result<String, ArcError> {
+    let document = compile(src, options)?;
+    match document.diagnostics.first_error() {
+        Some(error) => Err(error),
+        None => Ok(document.build_with_theme(&options.theme)),
+    }
+}
@@ -1,25 +1,47 @@
 mod args;
-mod funcs;
-mod lexer;
-mod parse;
-mod test;
-mod utilities;
-use args::command::{Args, Commands::*};
-use args::methods::{build, compile, help, render, write};
+use arc::{show_err, show_err_json};
+use args::command::{Args, Commands::*, MessageFormat};
+#[cfg(feature = "pdf")]
+use args::methods::build;
+#[cfg(feature = "preview")]
+use args::methods::render;
+use args::methods::{check, compile, help, stats, write};
 use clap::Parser as _;
-use utilities::stdout::show_err;
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    let message_format = args.command.message_format();
 
     let res = match args.command {
-        Compile(compile_args) => compile(compile_args.file, compile_args.output).await,
-        Preview(render_args) => render(render_args.file).await,
-        Build(build_args) => build(build_args.file, build_args.output, build_args.html).await,
+        Compile(compile_args) => {
+            compile(
+                compile_args.file,
+                compile_args.output,
+                compile_args.diagnostics,
+            )
+            .await
+        }
+        Check(check_args) => check(check_args.file, check_args.diagnostics).await,
+        Stats(stats_args) => stats(stats_args.file, stats_args.diagnostics).await,
+        #[cfg(feature = "preview")]
+        Preview(render_args) => render(render_args.file, render_args.diagnostics).await,
+        #[cfg(feature = "pdf")]
+        Build(build_args) => {
+            build(
+                build_args.file,
+                build_args.output,
+                build_args.html,
+                build_args.diagnostics,
+            )
+            .await
+        }
         Help(help_args) => help(help_args.command),
         Write(write_args) => write(write_args.file),
     };
 
-    show_err(res);
+    match message_format {
+        MessageFormat::Human => show_err(res),
+        MessageFormat::Json => show_err_json(res),
+    }
 }
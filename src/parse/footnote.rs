@@ -0,0 +1,163 @@
+use super::node::ASTNode;
+use crate::lexer::span::Span;
+use crate::utilities::error::ArcError;
+use maud::{Markup, html};
+
+/// The footnotes of a document: the references to them, numbered in the order each
+/// label is first referenced, and their definitions, wherever they were written.
+#[derive(Debug, Default)]
+pub struct Footnotes {
+    references: Vec<Reference>,
+    notes: Vec<Footnote>,
+}
+
+#[derive(Debug)]
+struct Reference {
+    label: String,
+    count: usize,
+    span: Span,
+}
+
+#[derive(Debug)]
+pub struct Footnote {
+    pub label: String,
+    pub content: ASTNode,
+    pub span: Span,
+}
+
+impl Footnotes {
+    /// Records a reference to `label` and returns the note's number and which
+    /// reference to it this is, both counted from 1.
+    pub fn reference(&mut self, label: &str, span: Span) -> (usize, usize) {
+        match self.references.iter().position(|r| r.label == label) {
+            Some(index) => {
+                self.references[index].count += 1;
+                (index + 1, self.references[index].count)
+            }
+            None => {
+                self.references.push(Reference {
+                    label: label.to_string(),
+                    count: 1,
+                    span,
+                });
+                (self.references.len(), 1)
+            }
+        }
+    }
+
+    /// Adds the definition of `label`; a second definition of the same label is
+    /// refused and the first one kept.
+    pub fn define(&mut self, label: String, content: ASTNode, span: Span) -> Result<(), ArcError> {
+        if self.notes.iter().any(|note| note.label == label) {
+            return Err(
+                ArcError::parser(format!("Footnote `[^{}]` is defined twice", label))
+                    .at(span)
+                    .with_help("the first definition is used"),
+            );
+        }
+        self.notes.push(Footnote {
+            label,
+            content,
+            span,
+        });
+        Ok(())
+    }
+
+    /// Warnings for references to notes that are never defined, and for notes that are
+    /// never referenced and so left out.
+    pub fn check(&self) -> Vec<ArcError> {
+        let undefined = self
+            .references
+            .iter()
+            .filter(|r| self.notes.iter().all(|note| note.label != r.label))
+            .map(|r| {
+                ArcError::parser(format!("Footnote `[^{}]` is never defined", r.label))
+                    .at(r.span)
+                    .with_help(format!(
+                        "define it on a line of its own: `[^{}]: text`",
+                        r.label
+                    ))
+            });
+        let unreferenced = self
+            .notes
+            .iter()
+            .filter(|note| self.references.iter().all(|r| r.label != note.label))
+            .map(|note| {
+                ArcError::parser(format!("Footnote `[^{}]` is never referenced", note.label))
+                    .at(note.span)
+                    .with_help(format!("reference it in the text with `[^{}]`", note.label))
+            });
+        undefined.chain(unreferenced).collect()
+    }
+
+    /// The defined notes that are referenced, with their numbers, in number order.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &Footnote)> {
+        self.references.iter().enumerate().filter_map(|(index, r)| {
+            self.notes
+                .iter()
+                .find(|note| note.label == r.label)
+                .map(|note| (index + 1, note))
+        })
+    }
+
+    /// The numbered list of notes that ends the document, each linking back to where
+    /// it is first referenced. Items carry their number, as a reference to a note that
+    /// is never defined still takes one. With sidenotes it is only shown on narrow
+    /// screens.
+    pub fn build(&self, sidenotes: bool) -> Markup {
+        if self.numbered().next().is_none() {
+            return html! {};
+        }
+        let class = if sidenotes {
+            "footnotes with-sidenotes"
+        } else {
+            "footnotes"
+        };
+        html! {
+            section class=(class) {
+                ol {
+                    @for (number, note) in self.numbered() {
+                        li id=(format!("fn-{}", number)) value=(number) {
+                            (note.content.build())
+                            a class="footnote-back" href=(format!("#fnref-{}", number)) { "↩" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gives the first reference to each defined note a copy of it, to be shown in the
+    /// margin next to the reference.
+    pub fn place_sidenotes(&self, node: &mut ASTNode) {
+        match node {
+            ASTNode::FootnoteReference {
+                number,
+                occurrence: 1,
+                sidenote,
+                ..
+            } => {
+                *sidenote = self
+                    .numbered()
+                    .find(|(n, _)| n == number)
+                    .map(|(_, note)| Box::new(note.content.clone()));
+            }
+            node => node
+                .children_mut()
+                .into_iter()
+                .for_each(|child| self.place_sidenotes(child)),
+        }
+    }
+}
+
+/// A superscript number linking to the note. Every reference gets its own anchor, the
+/// first one plain so the note can link back to it.
+pub fn build_reference(number: usize, occurrence: usize) -> Markup {
+    let id = match occurrence {
+        1 => format!("fnref-{}", number),
+        _ => format!("fnref-{}-{}", number, occurrence),
+    };
+    html! {
+        sup class="footnote-ref" { a href=(format!("#fn-{}", number)) id=(id) { (number) } }
+    }
+}
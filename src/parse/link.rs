@@ -0,0 +1,22 @@
+use crate::utilities::regexes;
+
+/// Turns a link target as written into the `href` of the rendered page. Links to other
+/// arc documents point at their compiled `.html`, and `host:port` gets an `http://`
+/// scheme so the browser does not read the host as one.
+pub fn resolve_href(target: &str) -> String {
+    let has_scheme = ["http://", "https://", "mailto:", "tel:"]
+        .iter()
+        .any(|scheme| target.starts_with(scheme));
+    if has_scheme {
+        return target.to_string();
+    }
+    if let Ok(true) = regexes::HOST_PORT.is_match(target) {
+        return format!("http://{}", target);
+    }
+    let path_end = target.find(['?', '#']).unwrap_or(target.len());
+    let (path, rest) = target.split_at(path_end);
+    match path.strip_suffix(".arc") {
+        Some(stem) => format!("{}.html{}", stem, rest),
+        None => target.to_string(),
+    }
+}
@@ -0,0 +1,155 @@
+use super::node::{ASTNode, ListMarker, Numbering, StyledSyntax};
+use crate::lexer::span::Span;
+use std::collections::VecDeque;
+
+/// Turns a run of list items, in source order, into lists nested by indentation. An
+/// item indented deeper than the one before it starts a list under that item; a change
+/// between ordered and unordered at the same depth starts a new list.
+pub fn nest_lists(items: Vec<ASTNode>) -> Vec<ASTNode> {
+    let mut items: VecDeque<ASTNode> = items.into();
+    let mut lists: Vec<ASTNode> = Vec::new();
+    while !items.is_empty() {
+        lists.push(collect_list(&mut items));
+    }
+    lists
+}
+
+fn collect_list(items: &mut VecDeque<ASTNode>) -> ASTNode {
+    let first = items.front().expect("Called with at least one item.");
+    let (ordered, indent) = shape(first);
+    let marker = match first {
+        ASTNode::ListItem { marker, .. } => *marker,
+        _ => None,
+    };
+    let mut list: Vec<ASTNode> = Vec::new();
+
+    while let Some(next) = items.front() {
+        let (next_ordered, next_indent) = shape(next);
+        if next_indent < indent || (next_indent == indent && next_ordered != ordered) {
+            break;
+        }
+        if next_indent > indent
+            && let Some(ASTNode::ListItem { children, .. }) = list.last_mut()
+        {
+            children.push(collect_list(items));
+            continue;
+        }
+        list.push(items.pop_front().expect("Checked front item exists."));
+    }
+
+    let span = match (list.first(), list.last()) {
+        (Some(first), Some(last)) => first.span().to(&last.span()),
+        _ => Span::default(),
+    };
+    ASTNode::List {
+        marker,
+        items: list,
+        span,
+    }
+}
+
+/// Lets an ordered list that starts from the first number pick up where the one before
+/// it left off when a single paragraph line is all that separates them, so
+/// `1. a\n2. b\nAn aside\n1. c` numbers `c` as 3.
+pub fn continue_numbering(nodes: &mut [Vec<ASTNode>]) {
+    for index in 2..nodes.len() {
+        let next = match (&nodes[index - 2][..], &nodes[index - 1][..]) {
+            (
+                [
+                    ASTNode::List {
+                        marker: Some(marker),
+                        items,
+                        ..
+                    },
+                ],
+                [paragraph],
+            ) if is_paragraph(paragraph) => {
+                let count = u32::try_from(items.len()).ok();
+                match count.and_then(|count| marker.start.checked_add(count)) {
+                    Some(next) => (marker.numbering, next),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        if let [
+            ASTNode::List {
+                marker: Some(marker),
+                ..
+            },
+        ] = &mut nodes[index][..]
+            && marker.numbering == next.0
+            && marker.start == 1
+        {
+            marker.start = next.1;
+        }
+    }
+}
+
+fn is_paragraph(node: &ASTNode) -> bool {
+    match node {
+        ASTNode::Inline { syntax, .. } => {
+            !syntax.iter().any(|s| matches!(s, StyledSyntax::Heading(_)))
+        }
+        _ => false,
+    }
+}
+
+fn shape(item: &ASTNode) -> (bool, usize) {
+    match item {
+        ASTNode::ListItem { marker, indent, .. } => (marker.is_some(), *indent),
+        _ => (false, 0),
+    }
+}
+
+/// Reads an ordered list marker as written in the source, e.g. `5.`, `c.`, `iv.` or
+/// `(2)`. A lone `i`, `v` or `x` counts as a roman numeral. Numbers too large for a
+/// `u32` are clamped to its maximum.
+pub fn parse_marker(marker: &str) -> ListMarker {
+    if let Some(number) = marker.strip_prefix('(') {
+        return ListMarker {
+            numbering: Numbering::Parenthesized,
+            start: number.trim_end_matches(')').parse().unwrap_or(u32::MAX),
+        };
+    }
+    let marker = marker.trim_end_matches('.');
+    if marker.bytes().all(|b| b.is_ascii_digit()) {
+        return ListMarker {
+            numbering: Numbering::Decimal,
+            start: marker.parse().unwrap_or(u32::MAX),
+        };
+    }
+    if let Some(start) = roman(marker) {
+        let numbering = if marker.starts_with(|c: char| c.is_ascii_lowercase()) {
+            Numbering::LowerRoman
+        } else {
+            Numbering::UpperRoman
+        };
+        return ListMarker { numbering, start };
+    }
+    let letter = marker.chars().next().unwrap_or('a');
+    ListMarker {
+        numbering: Numbering::LowerAlpha,
+        start: (letter.to_ascii_lowercase() as u32).saturating_sub('a' as u32) + 1,
+    }
+}
+
+fn roman(numeral: &str) -> Option<u32> {
+    let digits: Vec<u32> = numeral
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'i' => Some(1),
+            'v' => Some(5),
+            'x' => Some(10),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    let mut total: i64 = 0;
+    for (index, digit) in digits.iter().enumerate() {
+        match digits.get(index + 1) {
+            Some(next) if next > digit => total -= *digit as i64,
+            _ => total += *digit as i64,
+        }
+    }
+    u32::try_from(total).ok().filter(|total| *total > 0)
+}
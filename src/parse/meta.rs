@@ -1,4 +1,5 @@
 use crate::utilities::color::Color;
+use crate::utilities::error::{ArcError, ErrorKind};
 use maud::html;
 
 #[derive(Debug, PartialEq)]
@@ -19,28 +20,43 @@ pub enum MetaProperties {
     H3TagFontColor(Color),
     H4TagFontSize(u8),
     H4TagFontColor(Color),
+    H5TagFontSize(u8),
+    H5TagFontColor(Color),
+    H6TagFontSize(u8),
+    H6TagFontColor(Color),
+    PdfDetails(bool),
+    Sidenotes(bool),
+    Toc(bool),
+    TocDepth(u8),
 }
 
 impl MetaProperties {
-    pub fn new(string: String) -> Option<Self> {
+    pub fn new(string: String) -> Result<Self, ArcError> {
         let parts: Vec<&str> = string.splitn(2, "=").collect();
         if parts.len() != 2 {
-            crate::warn!("Runtime Warning: Invalid <meta /> property: {}", string);
-            return None;
+            return Err(Self::invalid_property(&string));
         }
         let key = parts[0].trim();
         let value = parts[1].trim();
 
         if value.is_empty() || key.is_empty() {
-            crate::warn!("Runtime Warning: Invalid <meta /> property: {}", string);
-            return None;
+            return Err(Self::invalid_property(&string));
         }
         Self::convert_meta_result(key, value)
     }
 
+    fn invalid_property(string: &str) -> ArcError {
+        ArcError::meta(format!("Invalid <meta /> property: {}", string))
+            .with_help("meta properties are written as `<meta key=value />`")
+    }
+
     pub fn build(&self) -> String {
         let result = match self {
-            MetaProperties::Name(_) => String::new(),
+            MetaProperties::Name(_)
+            | MetaProperties::PdfDetails(_)
+            | MetaProperties::Sidenotes(_)
+            | MetaProperties::Toc(_)
+            | MetaProperties::TocDepth(_) => String::new(),
             MetaProperties::Title(title) => html! { title { (title) } }.into_string(),
             MetaProperties::FontFamily(family) => {
                 html! { style { "* { font-family: " (family) "; }" } }.into_string()
@@ -85,54 +101,110 @@ impl MetaProperties {
             MetaProperties::H4TagFontColor(color) => {
                 html! { style { ".h4size { color: " (color.build()) "; }" } }.into_string()
             }
+            MetaProperties::H5TagFontSize(size) => {
+                html!({ style { ".h5size { font-size: " (size) "px !important; }" } }).into_string()
+            }
+            MetaProperties::H5TagFontColor(color) => {
+                html! { style { ".h5size { color: " (color.build()) "; }" } }.into_string()
+            }
+            MetaProperties::H6TagFontSize(size) => {
+                html!({ style { ".h6size { font-size: " (size) "px !important; }" } }).into_string()
+            }
+            MetaProperties::H6TagFontColor(color) => {
+                html! { style { ".h6size { color: " (color.build()) "; }" } }.into_string()
+            }
         };
         result
     }
 
-    fn convert_meta_result(key: &str, value: &str) -> Option<MetaProperties> {
+    fn convert_meta_result(key: &str, value: &str) -> Result<MetaProperties, ArcError> {
         match key {
-            "name" => Some(MetaProperties::Name(String::from(value))),
-            "title" => Some(MetaProperties::Title(String::from(value))),
-            "font-family" => Some(MetaProperties::FontFamily(String::from(value))),
-            "font-size" => Some(MetaProperties::FontSize(Self::str_to_u8(value)?)),
-            "font-color" => Some(MetaProperties::FontColor(Self::str_to_color(value)?)),
-            "background-color" => Some(MetaProperties::BackgroundColor(Self::str_to_color(value)?)),
-            "text-font-size" => Some(MetaProperties::PTagFontSize(Self::str_to_u8(value)?)),
-            "text-color" => Some(MetaProperties::PTagFontColor(Self::str_to_color(value)?)),
-            "h1-font-size" => Some(MetaProperties::H1TagFontSize(Self::str_to_u8(value)?)),
-            "h1-font-color" => Some(MetaProperties::H1TagFontColor(Self::str_to_color(value)?)),
-            "h2-font-size" => Some(MetaProperties::H2TagFontSize(Self::str_to_u8(value)?)),
-            "h2-font-color" => Some(MetaProperties::H2TagFontColor(Self::str_to_color(value)?)),
-            "h3-font-size" => Some(MetaProperties::H3TagFontSize(Self::str_to_u8(value)?)),
-            "h3-font-color" => Some(MetaProperties::H3TagFontColor(Self::str_to_color(value)?)),
-            "h4-font-size" => Some(MetaProperties::H4TagFontSize(Self::str_to_u8(value)?)),
-            "h4-font-color" => Some(MetaProperties::H4TagFontColor(Self::str_to_color(value)?)),
-            _ => {
-                crate::warn!("Runtime Warning: Unrecognized <meta /> property: {}", key);
-                None
-            }
+            "name" => Ok(MetaProperties::Name(String::from(value))),
+            "title" => Ok(MetaProperties::Title(String::from(value))),
+            "font-family" => Ok(MetaProperties::FontFamily(String::from(value))),
+            "font-size" => Ok(MetaProperties::FontSize(Self::str_to_u8(value)?)),
+            "font-color" => Ok(MetaProperties::FontColor(Self::str_to_color(value)?)),
+            "background-color" => Ok(MetaProperties::BackgroundColor(Self::str_to_color(value)?)),
+            "text-font-size" => Ok(MetaProperties::PTagFontSize(Self::str_to_u8(value)?)),
+            "text-color" => Ok(MetaProperties::PTagFontColor(Self::str_to_color(value)?)),
+            "h1-font-size" => Ok(MetaProperties::H1TagFontSize(Self::str_to_u8(value)?)),
+            "h1-font-color" => Ok(MetaProperties::H1TagFontColor(Self::str_to_color(value)?)),
+            "h2-font-size" => Ok(MetaProperties::H2TagFontSize(Self::str_to_u8(value)?)),
+            "h2-font-color" => Ok(MetaProperties::H2TagFontColor(Self::str_to_color(value)?)),
+            "h3-font-size" => Ok(MetaProperties::H3TagFontSize(Self::str_to_u8(value)?)),
+            "h3-font-color" => Ok(MetaProperties::H3TagFontColor(Self::str_to_color(value)?)),
+            "h4-font-size" => Ok(MetaProperties::H4TagFontSize(Self::str_to_u8(value)?)),
+            "h4-font-color" => Ok(MetaProperties::H4TagFontColor(Self::str_to_color(value)?)),
+            "h5-font-size" => Ok(MetaProperties::H5TagFontSize(Self::str_to_u8(value)?)),
+            "h5-font-color" => Ok(MetaProperties::H5TagFontColor(Self::str_to_color(value)?)),
+            "h6-font-size" => Ok(MetaProperties::H6TagFontSize(Self::str_to_u8(value)?)),
+            "h6-font-color" => Ok(MetaProperties::H6TagFontColor(Self::str_to_color(value)?)),
+            "pdf-details" => Ok(MetaProperties::PdfDetails(Self::str_to_details(value)?)),
+            "footnotes" => Ok(MetaProperties::Sidenotes(Self::str_to_sidenotes(value)?)),
+            "toc" => Ok(MetaProperties::Toc(Self::str_to_bool(value)?)),
+            "toc-depth" => Ok(MetaProperties::TocDepth(Self::str_to_level(value)?)),
+            _ => Err(ArcError::meta(format!(
+                "Unrecognized <meta /> property: {}",
+                key
+            ))),
         }
     }
 
-    fn str_to_color(str: &str) -> Option<Color> {
-        let result = Color::from_string(str.to_string());
-        let Ok(color) = result else {
-            crate::warn!("Runtime Warning: {}", result.unwrap_err());
-            return None;
-        };
-        Some(color)
+    fn str_to_color(str: &str) -> Result<Color, ArcError> {
+        Color::from_string(str.to_string()).map_err(|e| ArcError {
+            kind: ErrorKind::Meta,
+            ..e
+        })
     }
 
-    fn str_to_u8(str: &str) -> Option<u8> {
-        let result = str.parse::<u8>();
+    /// Whether details sections are expanded in PDF builds.
+    fn str_to_details(str: &str) -> Result<bool, ArcError> {
+        match str {
+            "expanded" => Ok(true),
+            "collapsed" => Ok(false),
+            _ => Err(ArcError::meta(format!(
+                "Invalid value for meta property pdf-details: {}",
+                str
+            ))
+            .with_help("use `expanded` or `collapsed`")),
+        }
+    }
 
-        let Ok(result) = result else {
-            crate::warn!(
-                "Runtime Warning: Invalid integer value for meta property: {}",
+    /// Whether footnotes are shown as sidenotes.
+    fn str_to_sidenotes(str: &str) -> Result<bool, ArcError> {
+        match str {
+            "sidenotes" => Ok(true),
+            "endnotes" => Ok(false),
+            _ => Err(ArcError::meta(format!(
+                "Invalid value for meta property footnotes: {}",
                 str
-            );
-            return None;
-        };
-        Some(result)
+            ))
+            .with_help("use `endnotes` or `sidenotes`")),
+        }
+    }
+
+    fn str_to_bool(str: &str) -> Result<bool, ArcError> {
+        str.parse::<bool>().map_err(|_| {
+            ArcError::meta(format!("Invalid boolean value for meta property: {}", str))
+                .with_help("use `true` or `false`")
+        })
+    }
+
+    /// A heading level, from 1 to 6.
+    fn str_to_level(str: &str) -> Result<u8, ArcError> {
+        match str.parse::<u8>() {
+            Ok(level @ 1..=6) => Ok(level),
+            _ => Err(
+                ArcError::meta(format!("Invalid heading level for meta property: {}", str))
+                    .with_help("heading levels go from 1 to 6"),
+            ),
+        }
+    }
+
+    fn str_to_u8(str: &str) -> Result<u8, ArcError> {
+        str.parse::<u8>().map_err(|_| {
+            ArcError::meta(format!("Invalid integer value for meta property: {}", str))
+                .with_help("sizes are whole numbers between 0 and 255")
+        })
     }
 }
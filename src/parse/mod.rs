@@ -1,5 +1,10 @@
+pub mod footnote;
+pub mod link;
+mod list;
 pub mod meta;
 pub mod node;
 pub mod parse;
+mod quote;
 mod table;
+pub mod toc;
 pub mod tree;
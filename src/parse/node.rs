@@ -1,29 +1,114 @@
+use super::footnote::build_reference;
+use super::toc::DEFAULT_DEPTH;
+use crate::lexer::span::Span;
 use crate::utilities::color::Color;
+use crate::utilities::error::ArcError;
 use maud::{Markup, PreEscaped, html};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ASTNode {
     Inline {
         syntax: Vec<StyledSyntax>,
         content: Vec<ASTNode>,
+        span: Span,
     },
     BlockedContent {
         content: BlockedContent,
+        span: Span,
     },
+    /// A link. `content` is its text, either the string after the target or a `\(...)`
+    /// group; without any, the target itself is shown.
+    Link {
+        href: String,
+        title: Option<String>,
+        new_tab: bool,
+        content: Vec<ASTNode>,
+        span: Span,
+    },
+    /// A `[^label]` reference to a footnote. `occurrence` counts the references to the
+    /// same note, so each gets its own anchor. `sidenote` is the note itself, shown in
+    /// the margin next to the first reference when the document uses sidenotes.
+    FootnoteReference {
+        number: usize,
+        occurrence: usize,
+        sidenote: Option<Box<ASTNode>>,
+        span: Span,
+    },
+    /// A run of list items at one indentation level. `marker` is the marker of the
+    /// first item, `None` for unordered lists.
     List {
+        marker: Option<ListMarker>,
+        items: Vec<ASTNode>,
+        span: Span,
+    },
+    /// One list item. `indent` is the width of the whitespace before its marker, and
+    /// `children` holds the lists indented under it. `task` is whether a `- [ ]` or
+    /// `- [x]` item is done, `None` for plain items.
+    ListItem {
+        syntax: Vec<StyledSyntax>,
+        content: Vec<ASTNode>,
+        children: Vec<ASTNode>,
+        marker: Option<ListMarker>,
+        task: Option<bool>,
+        indent: usize,
+        span: Span,
+    },
+    /// A quotation. `content` holds its lines and the quotes nested in it, and
+    /// `attribution` the `> --` line naming the source, if any.
+    BlockQuote {
+        content: Vec<ASTNode>,
+        attribution: Option<Box<ASTNode>>,
+        span: Span,
+    },
+    /// One quoted line before it is grouped into a quote. `depth` counts its `>`
+    /// markers, and `attribution` is whether it is a `> --` line.
+    QuoteLine {
         syntax: Vec<StyledSyntax>,
         content: Vec<ASTNode>,
+        depth: usize,
+        attribution: bool,
+        span: Span,
+    },
+    /// A `--- note!` style box. `content` holds the lines of its body, parsed as a
+    /// document of its own; `title` replaces the kind's name in the heading.
+    Callout {
+        kind: CalloutKind,
+        title: Option<String>,
+        content: Vec<Vec<ASTNode>>,
+        span: Span,
+    },
+    /// A `--- details!` section, collapsed behind its summary until clicked. When
+    /// `expanded` it is rendered as a regular section instead, as in PDF builds.
+    Details {
+        summary: Option<String>,
+        content: Vec<Vec<ASTNode>>,
+        expanded: bool,
+        span: Span,
+    },
+    /// A `<toc />` placeholder, filled in with the document's headings once the whole
+    /// document is parsed. Headings deeper than `depth` are left out.
+    TableOfContents {
+        depth: Option<u8>,
+        entries: Vec<TocEntry>,
+        span: Span,
     },
     Indicator {
         indicate: Indicator,
+        span: Span,
     },
     Table {
         position: (Option<f32>, Option<f32>),
         content: Vec<Vec<TableContent>>,
+        span: Span,
+    },
+    /// Placeholder for content that failed to parse; the error itself is in the diagnostics.
+    Error {
+        message: String,
+        span: Span,
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableContent {
     content: Vec<ASTNode>,
     is_heading: bool,
@@ -32,48 +117,124 @@ pub struct TableContent {
     colspan: u16,
 }
 
-#[derive(Debug)]
+/// A heading as listed in a table of contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub anchor: String,
+    pub text: String,
+}
+
+/// Font color, font size and background color of a `%[...]` style.
+pub type Style = (Option<Color>, Option<u8>, Option<Color>);
+
+#[derive(Debug, Clone)]
 pub enum StyledSyntax {
-    Style((Option<Color>, Option<u8>, Option<Color>)),
+    Style(Style),
     Heading(u8),
+    /// The `id` of a heading, for links from a table of contents.
+    Anchor(String),
+    Bold,
     Italic,
+    Strikethrough,
+    Underline,
+    Superscript,
+    Subscript,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BlockedContent {
-    Bold(String),
     Definition(String, String),
-    Link(String, Option<String>),
     PlainText(String),
     BlockMath(String),
     InlineMath(String),
     CodeBlock(String, String),
+    InlineCode(String),
+    /// Source, alt text, size and caption.
+    Image(String, String, (Option<f32>, Option<f32>), Option<String>),
     HTMLContainer(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalloutKind {
+    Note,
+    Warning,
+    Tip,
+    Danger,
+}
+
+impl CalloutKind {
+    /// Reads the kind named in a `--- note!` header.
+    pub fn new(name: &str) -> Option<Self> {
+        match name {
+            "note" => Some(CalloutKind::Note),
+            "warning" => Some(CalloutKind::Warning),
+            "tip" => Some(CalloutKind::Tip),
+            "danger" => Some(CalloutKind::Danger),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalloutKind::Note => "note",
+            CalloutKind::Warning => "warning",
+            CalloutKind::Tip => "tip",
+            CalloutKind::Danger => "danger",
+        }
+    }
+
+    /// The heading of a callout written without a title.
+    pub fn title(&self) -> &'static str {
+        match self {
+            CalloutKind::Note => "Note",
+            CalloutKind::Warning => "Warning",
+            CalloutKind::Tip => "Tip",
+            CalloutKind::Danger => "Danger",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Indicator {
-    StartOfOrderedList,
-    StartOfUnorderedList,
-    EndOfOrderedList,
-    EndOfUnorderedList,
     HorizontalLine,
 }
 
-struct CSSAttrs {
-    class: Option<String>,
-    style: String,
+/// How an ordered list is numbered and where it starts counting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListMarker {
+    pub numbering: Numbering,
+    pub start: u32,
 }
 
-impl Default for CSSAttrs {
-    fn default() -> Self {
-        Self {
-            class: None,
-            style: String::new(),
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Numbering {
+    Decimal,       // 1.
+    LowerAlpha,    // a.
+    LowerRoman,    // i.
+    UpperRoman,    // I.
+    Parenthesized, // (1)
+}
+
+impl Numbering {
+    /// The `list-style-type` for this numbering, `None` for the browser default.
+    pub fn list_style_type(&self) -> Option<&'static str> {
+        match self {
+            Numbering::Decimal => None,
+            Numbering::LowerAlpha => Some("lower-alpha"),
+            Numbering::LowerRoman => Some("lower-roman"),
+            Numbering::UpperRoman => Some("upper-roman"),
+            Numbering::Parenthesized => Some("parenthesized"),
         }
     }
 }
 
+#[derive(Default)]
+struct CSSAttrs {
+    class: Option<String>,
+    style: String,
+}
+
 impl TableContent {
     pub fn new(content: Vec<ASTNode>, is_heading: bool, style: String) -> Self {
         Self {
@@ -104,16 +265,167 @@ impl TableContent {
 impl ASTNode {
     pub fn build(&self) -> Markup {
         match self {
-            ASTNode::BlockedContent { content } => Self::build_block_content(content),
-            ASTNode::Inline { syntax, content } => Self::build_inline(syntax, content),
-            ASTNode::List { syntax, content } => Self::build_list(syntax, content),
-            ASTNode::Indicator { indicate } => Self::match_indicator(indicate),
-            ASTNode::Table { position, content } => Self::build_table(position, content),
+            ASTNode::BlockedContent { content, .. } => Self::build_block_content(content),
+            ASTNode::Inline {
+                syntax, content, ..
+            } => Self::build_inline(syntax, content),
+            ASTNode::Link {
+                href,
+                title,
+                new_tab,
+                content,
+                ..
+            } => Self::build_link(href, title, *new_tab, content),
+            ASTNode::FootnoteReference {
+                number,
+                occurrence,
+                sidenote,
+                ..
+            } => html! {
+                (build_reference(*number, *occurrence))
+                @if let Some(note) = sidenote {
+                    span class="sidenote" { sup { (number) } (note.build()) }
+                }
+            },
+            ASTNode::List { marker, items, .. } => Self::build_list(marker, items),
+            ASTNode::ListItem {
+                syntax,
+                content,
+                children,
+                task,
+                ..
+            } => Self::build_list_item(syntax, content, children, *task),
+            ASTNode::BlockQuote {
+                content,
+                attribution,
+                ..
+            } => Self::build_blockquote(content, attribution),
+            ASTNode::QuoteLine {
+                syntax, content, ..
+            } => Self::build_inline(syntax, content),
+            ASTNode::Callout {
+                kind,
+                title,
+                content,
+                ..
+            } => Self::build_callout(kind, title, content),
+            ASTNode::Details {
+                summary,
+                content,
+                expanded,
+                ..
+            } => Self::build_details(summary, content, *expanded),
+            ASTNode::TableOfContents { depth, entries, .. } => Self::build_toc(*depth, entries),
+            ASTNode::Indicator { indicate, .. } => Self::match_indicator(indicate),
+            ASTNode::Table {
+                position, content, ..
+            } => Self::build_table(position, content),
+            ASTNode::Error { message, .. } => {
+                html! { span class="error" title=(message) { (message) } }
+            }
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            ASTNode::Inline { span, .. }
+            | ASTNode::BlockedContent { span, .. }
+            | ASTNode::Link { span, .. }
+            | ASTNode::FootnoteReference { span, .. }
+            | ASTNode::List { span, .. }
+            | ASTNode::ListItem { span, .. }
+            | ASTNode::BlockQuote { span, .. }
+            | ASTNode::QuoteLine { span, .. }
+            | ASTNode::Callout { span, .. }
+            | ASTNode::Details { span, .. }
+            | ASTNode::TableOfContents { span, .. }
+            | ASTNode::Indicator { span, .. }
+            | ASTNode::Table { span, .. }
+            | ASTNode::Error { span, .. } => *span,
+        }
+    }
+
+    /// Renders lines one after another with a line break between them, except after
+    /// a heading, quote, callout, details section or table of contents, which are
+    /// blocks already.
+    pub fn build_lines(lines: &[Vec<ASTNode>]) -> String {
+        let mut html = String::new();
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 && !lines[index - 1].last().is_some_and(ASTNode::is_block) {
+                html.push_str("<br />");
+            }
+            html.push_str(&Self::iter_build_content(line).into_string());
+        }
+        html
+    }
+
+    fn is_block(&self) -> bool {
+        match self {
+            ASTNode::Inline { syntax, .. } => {
+                syntax.iter().any(|s| matches!(s, StyledSyntax::Heading(_)))
+            }
+            ASTNode::BlockQuote { .. }
+            | ASTNode::Callout { .. }
+            | ASTNode::Details { .. }
+            | ASTNode::TableOfContents { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The nodes directly inside this one, including those in table cells and in the
+    /// bodies of callouts and details sections.
+    pub fn children_mut(&mut self) -> Vec<&mut ASTNode> {
+        match self {
+            ASTNode::Inline { content, .. }
+            | ASTNode::Link { content, .. }
+            | ASTNode::QuoteLine { content, .. } => content.iter_mut().collect(),
+            ASTNode::List { items, .. } => items.iter_mut().collect(),
+            ASTNode::ListItem {
+                content, children, ..
+            } => content.iter_mut().chain(children.iter_mut()).collect(),
+            ASTNode::BlockQuote {
+                content,
+                attribution,
+                ..
+            } => content
+                .iter_mut()
+                .chain(attribution.iter_mut().map(|node| &mut **node))
+                .collect(),
+            ASTNode::Callout { content, .. } | ASTNode::Details { content, .. } => {
+                content.iter_mut().flatten().collect()
+            }
+            ASTNode::Table { content, .. } => content
+                .iter_mut()
+                .flatten()
+                .flat_map(|cell| cell.content.iter_mut())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The text of a node without its markup, e.g. to make a heading's anchor.
+    pub fn text(&self) -> String {
+        match self {
+            ASTNode::Inline { content, .. } => content.iter().map(ASTNode::text).collect(),
+            ASTNode::Link { href, content, .. } if content.is_empty() => href.clone(),
+            ASTNode::Link { content, .. } => content.iter().map(ASTNode::text).collect(),
+            ASTNode::BlockedContent {
+                content:
+                    BlockedContent::PlainText(text)
+                    | BlockedContent::InlineCode(text)
+                    | BlockedContent::InlineMath(text),
+                ..
+            } => text.clone(),
+            _ => String::new(),
         }
     }
 
-    fn resolve_syntax(syntax: &Vec<StyledSyntax>) -> (String, String) {
-        let syntax = syntax
+    fn resolve_syntax(syntax: &[StyledSyntax]) -> (String, String) {
+        let decorations = syntax
+            .iter()
+            .filter_map(|s| s.decoration())
+            .collect::<Vec<&str>>();
+        let mut syntax = syntax
             .iter()
             .map(|s| s.build())
             .fold(CSSAttrs::default(), |mut a, n| {
@@ -121,11 +433,17 @@ impl ASTNode {
                 a.style = format!("{}{}", a.style, n.style);
                 a
             });
+        if !decorations.is_empty() {
+            syntax.style.push_str(&format!(
+                "text-decoration: {} !important;",
+                decorations.join(" ")
+            ));
+        }
 
         (syntax.class.unwrap_or(String::new()), syntax.style)
     }
 
-    fn iter_build_content(content: &Vec<ASTNode>) -> Markup {
+    fn iter_build_content(content: &[ASTNode]) -> Markup {
         PreEscaped(
             content
                 .iter()
@@ -137,10 +455,6 @@ impl ASTNode {
 
     fn match_indicator(indicator: &Indicator) -> Markup {
         match indicator {
-            Indicator::StartOfOrderedList => html! { (PreEscaped("<ol>")) },
-            Indicator::StartOfUnorderedList => html! { (PreEscaped("<ul>")) },
-            Indicator::EndOfOrderedList => html! { (PreEscaped("</ol>")) },
-            Indicator::EndOfUnorderedList => html! { (PreEscaped("</ul>")) },
             Indicator::HorizontalLine => html! { (PreEscaped("<hr />")) },
         }
     }
@@ -174,22 +488,188 @@ impl ASTNode {
         }
     }
 
-    fn build_list(syntax: &Vec<StyledSyntax>, content: &Vec<ASTNode>) -> Markup {
+    fn build_list(marker: &Option<ListMarker>, items: &[ASTNode]) -> Markup {
+        let items = Self::iter_build_content(items);
+        match marker {
+            Some(marker) => {
+                let start = (marker.start != 1).then_some(marker.start);
+                let style = marker
+                    .numbering
+                    .list_style_type()
+                    .map(|style| format!("list-style-type: {}", style));
+                html! { ol start=[start] style=[style] { (items) } }
+            }
+            None => html! { ul { (items) } },
+        }
+    }
+
+    fn build_list_item(
+        syntax: &[StyledSyntax],
+        content: &[ASTNode],
+        children: &[ASTNode],
+        task: Option<bool>,
+    ) -> Markup {
         let (class, style) = Self::resolve_syntax(syntax);
         let content = Self::iter_build_content(content);
-        html! { li class=(class) style=(style) { (content) } }
+        let children = Self::iter_build_content(children);
+        match task {
+            Some(done) => html! {
+                li class=(class) style=(style) {
+                    input type="checkbox" disabled checked[done];
+                    (content) (children)
+                }
+            },
+            None => html! { li class=(class) style=(style) { (content) (children) } },
+        }
+    }
+
+    /// Lines follow each other with a line break; nested quotes are blocks and need none.
+    fn build_blockquote(content: &[ASTNode], attribution: &Option<Box<ASTNode>>) -> Markup {
+        let mut lines = String::new();
+        for (index, node) in content.iter().enumerate() {
+            let after_line = index > 0 && !matches!(content[index - 1], ASTNode::BlockQuote { .. });
+            if after_line && !matches!(node, ASTNode::BlockQuote { .. }) {
+                lines.push_str("<br />");
+            }
+            lines.push_str(&node.build().into_string());
+        }
+        html! {
+            blockquote {
+                (PreEscaped(lines))
+                @if let Some(attribution) = attribution {
+                    cite { (attribution.build()) }
+                }
+            }
+        }
+    }
+
+    /// The icon comes from the stylesheet.
+    fn build_callout(
+        kind: &CalloutKind,
+        title: &Option<String>,
+        content: &[Vec<ASTNode>],
+    ) -> Markup {
+        let title = title.as_deref().unwrap_or(kind.title());
+        html! {
+            div class=(format!("callout callout-{}", kind.name())) {
+                div class="callout-title" { (title) }
+                div class="callout-body" { (PreEscaped(Self::build_lines(content))) }
+            }
+        }
+    }
+
+    fn build_details(summary: &Option<String>, content: &[Vec<ASTNode>], expanded: bool) -> Markup {
+        let summary = summary.as_deref().unwrap_or("Details");
+        let body = PreEscaped(Self::build_lines(content));
+        if expanded {
+            html! {
+                section class="details" {
+                    div class="details-summary" { (summary) }
+                    div class="details-body" { (body) }
+                }
+            }
+        } else {
+            html! {
+                details {
+                    summary { (summary) }
+                    div class="details-body" { (body) }
+                }
+            }
+        }
     }
 
-    fn build_inline(syntax: &Vec<StyledSyntax>, content: &Vec<ASTNode>) -> Markup {
+    fn build_toc(depth: Option<u8>, entries: &[TocEntry]) -> Markup {
+        let depth = depth.unwrap_or(DEFAULT_DEPTH);
+        let entries = entries
+            .iter()
+            .filter(|entry| entry.level <= depth)
+            .collect::<Vec<&TocEntry>>();
+        html! {
+            nav class="toc" {
+                @if !entries.is_empty() { (Self::build_toc_list(&entries)) }
+            }
+        }
+    }
+
+    /// Each entry at the shallowest level present starts an item; the deeper entries
+    /// after it are listed inside that item.
+    fn build_toc_list(entries: &[&TocEntry]) -> Markup {
+        let base = entries.iter().map(|entry| entry.level).min().unwrap_or(1);
+        let mut items = Vec::new();
+        let mut index = 0;
+        while index < entries.len() {
+            let end = entries[index + 1..]
+                .iter()
+                .position(|entry| entry.level <= base)
+                .map_or(entries.len(), |offset| index + 1 + offset);
+            items.push((entries[index], &entries[index + 1..end]));
+            index = end;
+        }
+        html! {
+            ol {
+                @for (entry, children) in items {
+                    li {
+                        a href=(format!("#{}", entry.anchor)) { (entry.text) }
+                        @if !children.is_empty() { (Self::build_toc_list(children)) }
+                    }
+                }
+            }
+        }
+    }
+
+    fn build_inline(syntax: &[StyledSyntax], content: &[ASTNode]) -> Markup {
         let (class, style) = Self::resolve_syntax(syntax);
         let content = Self::iter_build_content(content);
-        html! { span class=(class) style=(style) { (content) } }
+        let heading = syntax.iter().find_map(|s| match s {
+            StyledSyntax::Heading(level) => Some(*level),
+            _ => None,
+        });
+        let id = syntax.iter().find_map(|s| match s {
+            StyledSyntax::Anchor(anchor) => Some(anchor),
+            _ => None,
+        });
+        match heading {
+            Some(1) => html! { h1 id=[id] class=(class) style=(style) { (content) } },
+            Some(2) => html! { h2 id=[id] class=(class) style=(style) { (content) } },
+            Some(3) => html! { h3 id=[id] class=(class) style=(style) { (content) } },
+            Some(4) => html! { h4 id=[id] class=(class) style=(style) { (content) } },
+            Some(5) => html! { h5 id=[id] class=(class) style=(style) { (content) } },
+            Some(_) => html! { h6 id=[id] class=(class) style=(style) { (content) } },
+            None if syntax.iter().any(|s| matches!(s, StyledSyntax::Bold)) => {
+                html! { strong class=(class) style=(style) { (content) } }
+            }
+            None => html! { span class=(class) style=(style) { (content) } },
+        }
     }
 
-    fn build_link(src: &str, content: &Option<String>) -> Markup {
-        match content {
-            Some(content) => html! { a href=(src) { (content) } },
-            None => html! { a href=(src) { (src) } },
+    fn build_link(
+        href: &str,
+        title: &Option<String>,
+        new_tab: bool,
+        content: &[ASTNode],
+    ) -> Markup {
+        let content = if content.is_empty() {
+            html! { (href) }
+        } else {
+            Self::iter_build_content(content)
+        };
+        let target = new_tab.then_some("_blank");
+        let rel = new_tab.then_some("noopener noreferrer");
+        html! { a href=(href) title=[title] target=[target] rel=[rel] { (content) } }
+    }
+
+    fn build_image(
+        src: &str,
+        alt: &str,
+        size: &(Option<f32>, Option<f32>),
+        caption: &Option<String>,
+    ) -> Markup {
+        let style = (size != &(None, None)).then(|| Self::handle_table_position(size));
+        match caption {
+            Some(caption) => html! {
+                figure { img src=(src) alt=(alt) style=[style]; figcaption { (caption) } }
+            },
+            None => html! { img src=(src) alt=(alt) style=[style]; },
         }
     }
 
@@ -205,8 +685,6 @@ impl ASTNode {
 
     fn build_block_content(content: &BlockedContent) -> Markup {
         match content {
-            BlockedContent::Bold(src) => html! { strong { (src) } },
-            BlockedContent::Link(src, content) => Self::build_link(src, content),
             BlockedContent::PlainText(src) => html! { span { (src) } },
             BlockedContent::Definition(term, definition) => {
                 Self::build_definition(term, definition)
@@ -220,6 +698,10 @@ impl ASTNode {
             BlockedContent::CodeBlock(language, src) => {
                 html! { pre { code class=(format!("language-{}", language)) { (src) } } }
             }
+            BlockedContent::InlineCode(src) => html! { code { (src) } },
+            BlockedContent::Image(src, alt, size, caption) => {
+                Self::build_image(src, alt, size, caption)
+            }
             BlockedContent::HTMLContainer(src) => html! { (PreEscaped(src)) },
         }
     }
@@ -252,12 +734,38 @@ impl StyledSyntax {
                 class: None,
                 style: String::from("font-style: italic !important;"),
             },
+            // info: bold is rendered as `<strong>`, decorations are combined in
+            // `resolve_syntax`
+            StyledSyntax::Bold
+            | StyledSyntax::Strikethrough
+            | StyledSyntax::Underline
+            | StyledSyntax::Anchor(_) => CSSAttrs::default(),
+            StyledSyntax::Superscript => CSSAttrs {
+                class: None,
+                style: String::from(
+                    "vertical-align: super !important;font-size: smaller !important;",
+                ),
+            },
+            StyledSyntax::Subscript => CSSAttrs {
+                class: None,
+                style: String::from(
+                    "vertical-align: sub !important;font-size: smaller !important;",
+                ),
+            },
+        }
+    }
+
+    fn decoration(&self) -> Option<&'static str> {
+        match self {
+            StyledSyntax::Strikethrough => Some("line-through"),
+            StyledSyntax::Underline => Some("underline"),
+            _ => None,
         }
     }
 
-    pub fn new_style(src: String) -> Result<Self, String> {
+    pub fn new_style(src: String) -> Result<Self, ArcError> {
         if src.replace(":", "").is_empty() {
-            return Err(String::from("Invalid style syntax: Empty"));
+            return Err(ArcError::style("Invalid style syntax: Empty"));
         }
 
         let src: Vec<&str> = src.split(":").collect();
@@ -269,7 +777,7 @@ impl StyledSyntax {
         Ok(Self::Style(tuple))
     }
 
-    fn parse_src(src: Vec<&str>) -> Result<(Option<Color>, Option<u8>, Option<Color>), String> {
+    fn parse_src(src: Vec<&str>) -> Result<Style, ArcError> {
         match src.len() {
             1 => {
                 let color = Self::parse_color(src[0])?;
@@ -286,11 +794,14 @@ impl StyledSyntax {
                 let background = Self::parse_color(src[2])?;
                 Ok((color, size, background))
             }
-            _ => Err(format!("Invalid style syntax: {}", src.join(":"))),
+            _ => Err(
+                ArcError::style(format!("Invalid style syntax: {}", src.join(":")))
+                    .with_help("styles are written as `%[color:size:background]`"),
+            ),
         }
     }
 
-    fn parse_u8(value: &str) -> Result<Option<u8>, String> {
+    fn parse_u8(value: &str) -> Result<Option<u8>, ArcError> {
         if value.trim().is_empty() {
             return Ok(None);
         }
@@ -298,14 +809,14 @@ impl StyledSyntax {
         if let Ok(value) = result {
             return Ok(Some(value));
         }
-        Err(format!(
+        Err(ArcError::style(format!(
             "Invalid value for font size: '{}', msg:`{}`",
             value,
             result.unwrap_err()
-        ))
+        )))
     }
 
-    fn parse_color(value: &str) -> Result<Option<Color>, String> {
+    fn parse_color(value: &str) -> Result<Option<Color>, ArcError> {
         if value.trim().is_empty() {
             return Ok(None);
         }
@@ -1,15 +1,29 @@
+use super::footnote::Footnotes;
+use super::link::resolve_href;
+use super::list::{continue_numbering, nest_lists, parse_marker};
 use super::meta::MetaProperties;
-use super::node::{ASTNode, BlockedContent, Indicator, StyledSyntax};
-use super::table::parse_table;
+use super::node::{ASTNode, BlockedContent, CalloutKind, Indicator, StyledSyntax};
+use super::quote::nest_quotes;
+use super::table::{parse_position, parse_table};
 use super::tree::Document;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::lexer_lite::LexerLite;
+use crate::lexer::span::{SourceText, Span};
 use crate::lexer::token::{Token, TokenKind};
-use crate::utilities::constants::CODE_LANGUAGE_REGEX;
-use fancy_regex::Regex;
+use crate::lexer::traits::LexerTrait as _;
+use crate::utilities::deadline::Deadline;
+use crate::utilities::diagnostics::Diagnostics;
+use crate::utilities::error::ArcError;
+use crate::utilities::regexes;
+use maud::html;
 
 #[derive(Debug)]
 pub struct Parser {
     source: Vec<Token>,
     document: Document,
+    previous: Span,
+    deadline: Deadline,
+    safe_mode: bool,
 }
 
 impl Parser {
@@ -17,106 +31,70 @@ impl Parser {
         Self {
             source: source.into_iter().rev().collect(),
             document: Document::init(),
+            previous: Span::default(),
+            deadline: Deadline::none(),
+            safe_mode: false,
         }
     }
-    pub fn parse(mut self) -> Result<Document, String> {
-        let code_regex =
-            Regex::new(CODE_LANGUAGE_REGEX).expect("Hard coded regex should be valid.");
+
+    /// Seeds the parser with warnings collected by earlier stages; they end up in
+    /// `Document::diagnostics` together with the parser's own.
+    pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.document.diagnostics.extend(diagnostics);
+        self
+    }
+
+    /// Stops parsing with a timeout error once `deadline` passes. Table cells are
+    /// parsed under the same deadline.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// In safe mode raw HTML blocks are rendered as text and math is escaped, so the
+    /// output carries no markup from the source. Table cells inherit the setting.
+    pub fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn parse(self) -> Result<Document, ArcError> {
+        let document = self.parse_with_recovery()?;
+        match document.diagnostics.first_error() {
+            Some(error) => Err(error),
+            None => Ok(document),
+        }
+    }
+
+    /// Parses the whole token stream. A line that fails to parse is recorded as an
+    /// error in `Document::diagnostics` and replaced by an error node, and parsing
+    /// resumes on the next line.
+    pub fn parse_with_recovery(self) -> Result<Document, ArcError> {
+        let mut document = self.parse_nested()?;
+        for warning in document.footnotes.check() {
+            document.diagnostics.warn(warning);
+        }
+        document.outline();
+        document.place_sidenotes();
+        Ok(document)
+    }
+
+    /// Footnotes referenced and defined so far, which the content parsed next adds to.
+    pub fn with_footnotes(mut self, footnotes: Footnotes) -> Self {
+        self.document.footnotes = footnotes;
+        self
+    }
+
+    /// Like `parse_with_recovery`, for content nested in a document such as a table
+    /// cell or a callout body: its footnotes are only checked with the whole document's.
+    pub fn parse_nested(mut self) -> Result<Document, ArcError> {
         while !self.at_eof() {
             let mut line: Vec<ASTNode> = Vec::new();
             while !self.at_end_of_line() && !self.at_eof() {
-                match self.this_kind() {
-                    &TokenKind::MetaData => {
-                        self.parse_meta()?;
-                    }
-                    &TokenKind::OrderedList => {
-                        line.push(ASTNode::Indicator {
-                            indicate: Indicator::StartOfOrderedList,
-                        });
-                        let result = self.parse_line()?;
-                        line.push(result);
-                    }
-                    &TokenKind::UnorderedList => {
-                        line.push(ASTNode::Indicator {
-                            indicate: Indicator::StartOfUnorderedList,
-                        });
-                        let result = self.parse_line()?;
-                        line.push(result);
-                    }
-                    &TokenKind::Table => {
-                        let token = self.consume()?;
-                        let src = token.value.expect("Parser: Table should contain a value");
-                        parse_table(src, &mut self.document)?;
-                    }
-                    &TokenKind::BlockMath => {
-                        let token = self.consume()?;
-                        let src = token
-                            .value
-                            .expect("Parser: BlockMath should contain a value");
-                        line.push(ASTNode::BlockedContent {
-                            content: BlockedContent::BlockMath(src),
-                        });
-                    }
-                    &TokenKind::HorizontalLine => {
-                        let _ = self.consume()?;
-                        line.push(ASTNode::Indicator {
-                            indicate: Indicator::HorizontalLine,
-                        });
-                    }
-                    &TokenKind::HTMLContainer => {
-                        let token = self.consume()?;
-                        let src = token
-                            .value
-                            .expect("Parser: HTMLContainer should contain a value");
-                        line.push(ASTNode::BlockedContent {
-                            content: BlockedContent::HTMLContainer(src),
-                        });
-                    }
-                    &TokenKind::CodeBlock => {
-                        let token = self.consume()?;
-                        let src = token
-                            .value
-                            .expect("Parser: CodeBlock should contain a value");
-                        let src_split = src.split_once("\n");
-                        let Some((language, content)) = src_split else {
-                            crate::warn!("Runtime Warning: Invalid code block syntax: {}", src);
-                            continue;
-                        };
-                        let language = if let Ok(Some(capture)) = code_regex.captures(language) {
-                            capture
-                                .get(1)
-                                .expect("Hard coded regex should have a capture group.")
-                                .as_str()
-                        } else {
-                            crate::warn!(
-                                "Runtime Warning: Code block with no language specified, Source:\n`{}`",
-                                src.trim()
-                            );
-                            ""
-                        };
-                        line.push(ASTNode::BlockedContent {
-                            content: BlockedContent::CodeBlock(
-                                String::from(language),
-                                String::from(content.trim_end()),
-                            ),
-                        });
-                    }
-                    &TokenKind::String
-                    | &TokenKind::Link
-                    | &TokenKind::Definition
-                    | &TokenKind::Bold
-                    | &TokenKind::Italic
-                    | &TokenKind::Heading
-                    | &TokenKind::BackSlashLeftParenthesisInline
-                    | &TokenKind::RightParenthesis
-                    | &TokenKind::LiteralRightParenthesis
-                    | &TokenKind::InlineMath
-                    | &TokenKind::EndOfLine
-                    | &TokenKind::EOF
-                    | &TokenKind::CharacterStyle => {
-                        let result = self.parse_line()?;
-                        line.push(result);
-                    }
+                self.deadline.check()?;
+                if let Err(error) = self.parse_block(&mut line) {
+                    self.recover(error, &mut line);
                 }
             }
             if self.at_end_of_line() {
@@ -127,160 +105,316 @@ impl Parser {
         self.postprocess()
     }
 
-    fn postprocess(mut self) -> Result<Document, String> {
-        let mut new_nodes: Vec<Vec<ASTNode>> = Vec::new();
-        self.document.nodes = self.document.nodes.into_iter().rev().collect();
-        'outer: while !self.document.nodes.is_empty() {
-            let mut line = self
-                .document
-                .nodes
-                .pop()
-                .expect("Parser: pop not empty vector should be valid.");
-            if !line.is_empty()
-                && matches!(
-                    line[0],
-                    ASTNode::Indicator {
-                        indicate: Indicator::StartOfOrderedList
-                    }
-                )
-            {
-                let indicator = line.remove(0);
-
-                new_nodes.push(vec![indicator]);
-                new_nodes.push(line);
-
-                'inner: loop {
-                    if self.document.nodes.is_empty() {
-                        new_nodes.push(vec![ASTNode::Indicator {
-                            indicate: Indicator::EndOfOrderedList,
-                        }]);
-                        break 'outer;
-                    }
-                    let line_type: &Vec<ASTNode> =
-                        &self.document.nodes[self.document.nodes.len() - 1];
+    fn parse_block(&mut self, line: &mut Vec<ASTNode>) -> Result<(), ArcError> {
+        match self.this_kind() {
+            &TokenKind::MetaData => {
+                self.parse_meta()?;
+            }
+            &TokenKind::OrderedList | &TokenKind::UnorderedList => {
+                let item = self.parse_list_item()?;
+                line.push(item);
+            }
+            &TokenKind::TableOfContents => {
+                let token = self.consume()?;
+                let depth = token
+                    .value
+                    .as_deref()
+                    .and_then(|value| value.trim().strip_prefix("depth="))
+                    .and_then(|depth| depth.parse().ok());
+                line.push(ASTNode::TableOfContents {
+                    depth,
+                    entries: Vec::new(),
+                    span: token.span,
+                });
+            }
+            &TokenKind::FootnoteDefinition => {
+                self.parse_footnote_definition()?;
+            }
+            &TokenKind::Callout => {
+                let callout = self.parse_callout()?;
+                line.push(callout);
+            }
+            &TokenKind::Details => {
+                let details = self.parse_details()?;
+                line.push(details);
+            }
+            &TokenKind::BlockQuote => {
+                let quote = self.parse_quote_line()?;
+                line.push(quote);
+            }
+            &TokenKind::Table => {
+                let token = self.consume()?;
+                let src = token.value.expect("Parser: Table should contain a value");
+                parse_table(
+                    src,
+                    token.span,
+                    token.body,
+                    &mut self.document,
+                    self.deadline,
+                    self.safe_mode,
+                )?;
+            }
+            &TokenKind::BlockMath => {
+                let token = self.consume()?;
+                let src = token
+                    .value
+                    .expect("Parser: BlockMath should contain a value");
+                line.push(ASTNode::BlockedContent {
+                    content: BlockedContent::BlockMath(self.escape_if_safe(src)),
+                    span: token.span,
+                });
+            }
+            &TokenKind::HorizontalLine => {
+                let token = self.consume()?;
+                line.push(ASTNode::Indicator {
+                    indicate: Indicator::HorizontalLine,
+                    span: token.span,
+                });
+            }
+            &TokenKind::HTMLContainer => {
+                let token = self.consume()?;
+                let src = token
+                    .value
+                    .expect("Parser: HTMLContainer should contain a value");
+                let content = if self.safe_mode {
+                    self.document.diagnostics.warn(
+                        ArcError::parser("HTML blocks are rendered as text in safe mode")
+                            .at(token.span),
+                    );
+                    BlockedContent::PlainText(src)
+                } else {
+                    BlockedContent::HTMLContainer(src)
+                };
+                line.push(ASTNode::BlockedContent {
+                    content,
+                    span: token.span,
+                });
+            }
+            &TokenKind::CodeBlock => {
+                let token = self.consume()?;
+                let src = token
+                    .value
+                    .expect("Parser: CodeBlock should contain a value");
+                let src_split = src.split_once("\n");
+                let Some((language, content)) = src_split else {
+                    self.document.diagnostics.warn(
+                        ArcError::parser(format!("Invalid code block syntax: {}", src))
+                            .at(token.span),
+                    );
+                    return Ok(());
+                };
+                let language = if let Ok(Some(capture)) = regexes::CODE_LANGUAGE.captures(language)
+                {
+                    capture
+                        .get(1)
+                        .expect("Hard coded regex should have a capture group.")
+                        .as_str()
+                } else {
+                    self.document.diagnostics.warn(
+                        ArcError::parser("Code block with no language specified")
+                            .at(token.span)
+                            .with_help(
+                                "name the language on the opening line, e.g. `<code>:python`",
+                            ),
+                    );
+                    ""
+                };
+                line.push(ASTNode::BlockedContent {
+                    content: BlockedContent::CodeBlock(
+                        String::from(language),
+                        String::from(content.trim_end()),
+                    ),
+                    span: token.span,
+                });
+            }
+            &TokenKind::RightParenthesis => {
+                return Err(ArcError::parser("Unmatched `)`")
+                    .at(self.this_span())
+                    .with_help("write `\\)` for a literal parenthesis"));
+            }
+            &TokenKind::String
+            | &TokenKind::Link
+            | &TokenKind::FootnoteReference
+            | &TokenKind::Image
+            | &TokenKind::Definition
+            | &TokenKind::Bold
+            | &TokenKind::Italic
+            | &TokenKind::Strikethrough
+            | &TokenKind::Underline
+            | &TokenKind::Superscript
+            | &TokenKind::Subscript
+            | &TokenKind::Heading
+            | &TokenKind::BackSlashLeftParenthesisInline
+            | &TokenKind::LiteralRightParenthesis
+            | &TokenKind::InlineMath
+            | &TokenKind::InlineCode
+            | &TokenKind::EndOfLine
+            | &TokenKind::Eof
+            | &TokenKind::CharacterStyle
+            | &TokenKind::Error => {
+                let result = self.parse_line()?;
+                line.push(result);
+            }
+        }
+        Ok(())
+    }
 
-                    if line_type.is_empty() {
-                        let _ = self.document.nodes.pop();
-                        continue 'inner;
-                    }
+    fn recover(&mut self, error: ArcError, line: &mut Vec<ASTNode>) {
+        let span = error.span.unwrap_or(self.previous);
+        line.push(ASTNode::Error {
+            message: error.message.clone(),
+            span,
+        });
+        self.document.diagnostics.error(error);
+        while !self.at_end_of_line() && !self.at_eof() {
+            let _ = self.consume();
+        }
+    }
 
-                    if !matches!(
-                        line_type[0],
-                        ASTNode::Indicator {
-                            indicate: Indicator::StartOfOrderedList
-                        }
-                    ) {
-                        new_nodes.push(vec![ASTNode::Indicator {
-                            indicate: Indicator::EndOfOrderedList,
-                        }]);
-                        break 'inner;
-                    } else {
-                        let mut line = self
-                            .document
-                            .nodes
-                            .pop()
-                            .expect("Parser: pop not empty vector should be valid.");
-                        let _ = line.remove(0);
-                        new_nodes.push(line);
-                        continue 'inner;
-                    }
+    /// Gathers the list items of consecutive lines into nested lists, and quote lines
+    /// into nested quotes. Blank lines between items do not end a list, but they do end
+    /// a quote.
+    fn postprocess(mut self) -> Result<Document, ArcError> {
+        let mut nodes: Vec<Vec<ASTNode>> = Vec::new();
+        let mut items: Vec<ASTNode> = Vec::new();
+        let mut quotes: Vec<ASTNode> = Vec::new();
+        for line in std::mem::take(&mut self.document.nodes) {
+            match line.as_slice() {
+                [ASTNode::ListItem { .. }] => {
+                    Self::close_quote(&mut nodes, &mut quotes);
+                    items.extend(line);
                 }
-            } else if !line.is_empty()
-                && matches!(
-                    line[0],
-                    ASTNode::Indicator {
-                        indicate: Indicator::StartOfUnorderedList
-                    }
-                )
-            {
-                let indicator = line.remove(0);
-
-                new_nodes.push(vec![indicator]);
-                new_nodes.push(line);
-
-                'inner: loop {
-                    if self.document.nodes.is_empty() {
-                        new_nodes.push(vec![ASTNode::Indicator {
-                            indicate: Indicator::EndOfUnorderedList,
-                        }]);
-                        break 'outer;
-                    }
-                    let line_type: &Vec<ASTNode> =
-                        &self.document.nodes[self.document.nodes.len() - 1];
-
-                    if line_type.is_empty() {
-                        self.document.nodes.pop();
-                        continue 'inner;
-                    }
-
-                    if !matches!(
-                        line_type[0],
-                        ASTNode::Indicator {
-                            indicate: Indicator::StartOfUnorderedList
-                        }
-                    ) {
-                        new_nodes.push(vec![ASTNode::Indicator {
-                            indicate: Indicator::EndOfUnorderedList,
-                        }]);
-                        break 'inner;
-                    } else {
-                        let mut line = self
-                            .document
-                            .nodes
-                            .pop()
-                            .expect("Parser: pop not empty vector should be valid.");
-                        let _ = line.remove(0);
-                        new_nodes.push(line);
-                        continue 'inner;
-                    }
+                [ASTNode::QuoteLine { .. }] => {
+                    Self::close_lists(&mut nodes, &mut items);
+                    quotes.extend(line);
+                }
+                [] if !items.is_empty() => {}
+                _ => {
+                    Self::close_lists(&mut nodes, &mut items);
+                    Self::close_quote(&mut nodes, &mut quotes);
+                    nodes.push(line);
                 }
-            } else {
-                new_nodes.push(line);
             }
         }
-        self.document.nodes = new_nodes;
+        Self::close_lists(&mut nodes, &mut items);
+        Self::close_quote(&mut nodes, &mut quotes);
+        continue_numbering(&mut nodes);
+        self.document.nodes = nodes;
         Ok(self.document)
     }
+
+    fn close_lists(nodes: &mut Vec<Vec<ASTNode>>, items: &mut Vec<ASTNode>) {
+        nodes.extend(
+            nest_lists(std::mem::take(items))
+                .into_iter()
+                .map(|list| vec![list]),
+        );
+    }
+
+    fn close_quote(nodes: &mut Vec<Vec<ASTNode>>, quotes: &mut Vec<ASTNode>) {
+        if !quotes.is_empty() {
+            nodes.push(vec![nest_quotes(std::mem::take(quotes))]);
+        }
+    }
 }
 
 impl Parser {
-    fn parse_line(&mut self) -> Result<ASTNode, String> {
-        let is_list = if self.this_kind() == &TokenKind::OrderedList
-            || self.this_kind() == &TokenKind::UnorderedList
-        {
-            let _ = self.consume()?;
-            true
-        } else {
-            false
-        };
+    fn parse_line(&mut self) -> Result<ASTNode, ArcError> {
+        let start = self.this_span();
+        let syntax = self.parse_syntax()?;
+        let content = self.perform_parse()?;
+        Ok(ASTNode::Inline {
+            syntax,
+            content,
+            span: start.to(&self.previous),
+        })
+    }
 
+    fn parse_list_item(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let value = token
+            .value
+            .as_deref()
+            .expect("Parser: list marker should contain its indentation");
+        let marker = value.trim_start_matches([' ', '\t']);
+        let indent = value[..value.len() - marker.len()]
+            .chars()
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        let task = match marker {
+            "- [ ]" => Some(false),
+            "- [x]" | "- [X]" => Some(true),
+            _ => None,
+        };
+        let marker = match token.kind {
+            TokenKind::OrderedList => Some(parse_marker(marker)),
+            _ => None,
+        };
         let syntax = self.parse_syntax()?;
         let content = self.perform_parse()?;
+        Ok(ASTNode::ListItem {
+            syntax,
+            content,
+            children: Vec::new(),
+            marker,
+            task,
+            indent,
+            span: token.span.to(&self.previous),
+        })
+    }
 
-        if is_list {
-            Ok(ASTNode::List { syntax, content })
-        } else {
-            Ok(ASTNode::Inline { syntax, content })
-        }
+    fn parse_quote_line(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let marker = token
+            .value
+            .as_deref()
+            .expect("Parser: quote marker should contain its depth");
+        let depth = marker.matches('>').count();
+        let attribution = marker.ends_with("--");
+        let syntax = self.parse_syntax()?;
+        let content = self.perform_parse()?;
+        Ok(ASTNode::QuoteLine {
+            syntax,
+            content,
+            depth,
+            attribution,
+            span: token.span.to(&self.previous),
+        })
     }
 }
 
 impl Parser {
-    fn parse_syntax(&mut self) -> Result<Vec<StyledSyntax>, String> {
+    fn parse_syntax(&mut self) -> Result<Vec<StyledSyntax>, ArcError> {
         let mut syntax: Vec<StyledSyntax> = Vec::new();
         loop {
             match self.this_kind() {
-                &TokenKind::CharacterStyle => {
+                TokenKind::CharacterStyle => {
                     let result = self.parse_character_style()?;
                     if let Some(result) = result {
                         syntax.push(result);
                     }
                 }
-                &TokenKind::Italic => {
+                TokenKind::Italic => {
                     let _ = self.consume()?;
                     syntax.push(StyledSyntax::Italic);
                 }
-                &TokenKind::Heading => {
+                TokenKind::Strikethrough => {
+                    let _ = self.consume()?;
+                    syntax.push(StyledSyntax::Strikethrough);
+                }
+                TokenKind::Underline => {
+                    let _ = self.consume()?;
+                    syntax.push(StyledSyntax::Underline);
+                }
+                TokenKind::Superscript => {
+                    let _ = self.consume()?;
+                    syntax.push(StyledSyntax::Superscript);
+                }
+                TokenKind::Subscript => {
+                    let _ = self.consume()?;
+                    syntax.push(StyledSyntax::Subscript);
+                }
+                TokenKind::Heading => {
                     let token = self.consume()?;
                     let level = token
                         .value
@@ -294,22 +428,24 @@ impl Parser {
         Ok(syntax)
     }
 
-    fn parse_character_style(&mut self) -> Result<Option<StyledSyntax>, String> {
-        let src = self
-            .consume()?
+    fn parse_character_style(&mut self) -> Result<Option<StyledSyntax>, ArcError> {
+        let token = self.consume()?;
+        let src = token
             .value
             .expect("Parser: CharacterStyle should contain a value");
-        let result = StyledSyntax::new_style(src);
-        if let Err(err) = result {
-            crate::warn!("Runtime Warning: {}", err);
-            return Ok(None);
+        match StyledSyntax::new_style(src) {
+            Ok(style) => Ok(Some(style)),
+            Err(err) => {
+                self.document.diagnostics.warn(err.at(token.span));
+                Ok(None)
+            }
         }
-        Ok(Some(result.unwrap()))
     }
 
-    fn perform_parse(&mut self) -> Result<Vec<ASTNode>, String> {
+    fn perform_parse(&mut self) -> Result<Vec<ASTNode>, ArcError> {
         let mut content_element: Vec<ASTNode> = Vec::new();
         while self.this_kind() != &TokenKind::EndOfLine && !self.at_eof() {
+            self.deadline.check()?;
             let token_kind = self.this_kind();
             match token_kind {
                 &TokenKind::BackSlashLeftParenthesisInline => {
@@ -324,19 +460,35 @@ impl Parser {
                         content: BlockedContent::PlainText(
                             token.value.expect("Parser: String should contain a value"),
                         ),
+                        span: token.span,
                     });
                 }
                 &TokenKind::LiteralRightParenthesis => {
-                    let _ = self.consume()?;
+                    let token = self.consume()?;
                     content_element.push(ASTNode::BlockedContent {
                         content: BlockedContent::PlainText(String::from(")")),
+                        span: token.span,
                     });
                 }
                 &TokenKind::Bold => {
+                    content_element.push(self.parse_bold()?);
+                }
+                // info: a style in the middle of a line applies to the rest of the line
+                // or group, as one at its start would
+                &TokenKind::CharacterStyle => {
+                    content_element.push(self.parse_line()?);
+                }
+                // info: an emphasis marker after other inline content has nothing to
+                // style, so it is kept as text
+                &TokenKind::Italic
+                | &TokenKind::Strikethrough
+                | &TokenKind::Underline
+                | &TokenKind::Superscript
+                | &TokenKind::Subscript => {
                     let token = self.consume()?;
-                    let src = token.value.expect("Parser: Bold should contain a value");
                     content_element.push(ASTNode::BlockedContent {
-                        content: BlockedContent::Bold(src),
+                        content: BlockedContent::PlainText(token.value.unwrap_or_default()),
+                        span: token.span,
                     });
                 }
                 &TokenKind::Definition => {
@@ -347,23 +499,47 @@ impl Parser {
                     let src: Vec<&str> = src.split("-@[]").collect();
                     content_element.push(ASTNode::BlockedContent {
                         content: BlockedContent::Definition(src[0].to_string(), src[1].to_string()),
+                        span: token.span,
                     });
                 }
                 &TokenKind::Link => {
+                    content_element.push(self.parse_link()?);
+                }
+                &TokenKind::FootnoteReference => {
+                    let token = self.consume()?;
+                    let label = token
+                        .value
+                        .expect("Parser: FootnoteReference should contain a label");
+                    let (number, occurrence) =
+                        self.document.footnotes.reference(&label, token.span);
+                    content_element.push(ASTNode::FootnoteReference {
+                        number,
+                        occurrence,
+                        sidenote: None,
+                        span: token.span,
+                    });
+                }
+                &TokenKind::Image => {
                     let token = self.consume()?;
-                    let src = token.value.expect("Parser: Link should contain a value");
-                    let next_kind = self.this_kind();
-                    let content = if next_kind == &TokenKind::String {
-                        Some(
-                            self.consume()?
-                                .value
-                                .expect("Parser: String with no internal value"),
-                        )
-                    } else {
-                        None
-                    };
+                    let src = token.value.expect("Parser: Image should contain a value");
+                    let mut parts = src.split('\n');
+                    let mut part = || parts.next().unwrap_or_default().to_string();
+                    let (src, size, alt, caption) = (part(), part(), part(), part());
                     content_element.push(ASTNode::BlockedContent {
-                        content: BlockedContent::Link(src, content),
+                        content: BlockedContent::Image(
+                            src,
+                            alt,
+                            parse_position(&size),
+                            Some(caption).filter(|caption| !caption.is_empty()),
+                        ),
+                        span: token.span,
+                    });
+                }
+                &TokenKind::Error => {
+                    let token = self.consume()?;
+                    content_element.push(ASTNode::Error {
+                        message: token.value.unwrap_or_default(),
+                        span: token.span,
                     });
                 }
                 &TokenKind::InlineMath => {
@@ -372,53 +548,261 @@ impl Parser {
                         .value
                         .expect("Parser: InlineMath should contain a value");
                     content_element.push(ASTNode::BlockedContent {
-                        content: BlockedContent::InlineMath(src),
+                        content: BlockedContent::InlineMath(self.escape_if_safe(src)),
+                        span: token.span,
                     });
                 }
-                _ => unreachable!(),
+                &TokenKind::InlineCode => {
+                    let token = self.consume()?;
+                    let src = token
+                        .value
+                        .expect("Parser: InlineCode should contain a value");
+                    content_element.push(ASTNode::BlockedContent {
+                        content: BlockedContent::InlineCode(src),
+                        span: token.span,
+                    });
+                }
+                other => {
+                    return Err(ArcError::parser(format!(
+                        "Unexpected {:?} in the middle of a line",
+                        other
+                    ))
+                    .at(self.this_span())
+                    .with_help(
+                        "styles and blocks start a line; wrap inline styled text in `\\(...)`",
+                    ));
+                }
             }
         }
         Ok(content_element)
     }
 
-    fn parse_meta(&mut self) -> Result<(), String> {
-        let src = self
-            .consume()?
+    /// Link text is a `\(...)` group right after the target, parsed like any other
+    /// group, or else the plain string that follows.
+    fn parse_link(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let value = token.value.expect("Parser: Link should contain a value");
+        let mut parts = value.split('\n');
+        let href = resolve_href(parts.next().unwrap_or_default());
+        let title = parts
+            .next()
+            .filter(|title| !title.is_empty())
+            .map(String::from);
+        let new_tab = parts.next() == Some(">");
+        let content = match self.this_kind() {
+            TokenKind::BackSlashLeftParenthesisInline => {
+                let _ = self.consume()?;
+                let group = self.parse_line()?;
+                let _ = self.expect(TokenKind::RightParenthesis)?;
+                vec![group]
+            }
+            TokenKind::String => {
+                let text = self.consume()?;
+                vec![ASTNode::BlockedContent {
+                    content: BlockedContent::PlainText(
+                        text.value.expect("Parser: String with no internal value"),
+                    ),
+                    span: text.span,
+                }]
+            }
+            _ => Vec::new(),
+        };
+        Ok(ASTNode::Link {
+            href,
+            title,
+            new_tab,
+            content,
+            span: token.span.to(&self.previous),
+        })
+    }
+
+    /// Bold text is lexed as a single token, so its content is lexed again and parsed
+    /// like a `\(...)` group, with the bold style in front of its own.
+    fn parse_bold(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let src = token.value.expect("Parser: Bold should contain a value");
+        let tokens = LexerLite::new(src).with_base(token.span).tokenize()?;
+        let mut parser = Parser::new(tokens)
+            .with_footnotes(std::mem::take(&mut self.document.footnotes))
+            .with_deadline(self.deadline)
+            .with_safe_mode(self.safe_mode);
+        let parsed = parser
+            .parse_syntax()
+            .and_then(|syntax| Ok((syntax, parser.perform_parse()?)));
+        self.document.footnotes = std::mem::take(&mut parser.document.footnotes);
+        let (syntax, content) = parsed?;
+        if !parser.at_eof() {
+            return Err(ArcError::parser("Unmatched `)` in bold text")
+                .at(token.span)
+                .with_help("write `\\)` for a literal parenthesis"));
+        }
+        self.document
+            .diagnostics
+            .extend(parser.document.diagnostics);
+        Ok(ASTNode::Inline {
+            syntax: std::iter::once(StyledSyntax::Bold).chain(syntax).collect(),
+            content,
+            span: token.span,
+        })
+    }
+
+    fn parse_callout(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let src = token.value.expect("Parser: Callout should contain a value");
+        let (header, body) = src.split_once('\n').unwrap_or((&src, ""));
+        let (name, title) = Self::split_container_header(header, token.span)?;
+        let kind = CalloutKind::new(name).ok_or_else(|| {
+            ArcError::parser(format!("Unknown callout kind `{}`", name))
+                .at(token.span)
+                .with_help("use `note`, `tip`, `warning` or `danger`")
+        })?;
+        let body = token
+            .body
+            .unwrap_or_else(|| SourceText::new(body.to_string()));
+        let content = self.parse_container_body(body)?;
+        Ok(ASTNode::Callout {
+            kind,
+            title,
+            content,
+            span: token.span,
+        })
+    }
+
+    fn parse_details(&mut self) -> Result<ASTNode, ArcError> {
+        let token = self.consume()?;
+        let src = token.value.expect("Parser: Details should contain a value");
+        let (header, body) = src.split_once('\n').unwrap_or((&src, ""));
+        let (_, summary) = Self::split_container_header(header, token.span)?;
+        let body = token
+            .body
+            .unwrap_or_else(|| SourceText::new(body.to_string()));
+        let content = self.parse_container_body(body)?;
+        Ok(ASTNode::Details {
+            summary,
+            content,
+            expanded: false,
+            span: token.span,
+        })
+    }
+
+    /// Splits a `--- kind! text` header into the kind and the text, `None` when there
+    /// is no text.
+    fn split_container_header(
+        header: &str,
+        span: Span,
+    ) -> Result<(&str, Option<String>), ArcError> {
+        let (name, text) = header
+            .trim_start_matches('-')
+            .trim_start()
+            .split_once('!')
+            .ok_or_else(|| {
+                ArcError::parser("Container header is missing the `!` after its kind")
+                    .at(span)
+                    .with_help("write the header as `--- note!` or `--- details! summary`")
+            })?;
+        let text = Some(text.trim())
+            .filter(|text| !text.is_empty())
+            .map(String::from);
+        Ok((name, text))
+    }
+
+    /// Parses the body of a callout or details section as a document of its own, so it
+    /// can hold anything a document can, including tables and further callouts and
+    /// details sections. Its metadata and diagnostics go to the enclosing document.
+    fn parse_container_body(&mut self, body: SourceText) -> Result<Vec<Vec<ASTNode>>, ArcError> {
+        let (tokens, diagnostics) = Lexer::nested(body)
+            .with_deadline(self.deadline)
+            .tokenize_with_diagnostics()?;
+        let mut document = Parser::new(tokens)
+            .with_diagnostics(diagnostics)
+            .with_footnotes(std::mem::take(&mut self.document.footnotes))
+            .with_deadline(self.deadline)
+            .with_safe_mode(self.safe_mode)
+            .parse_nested()?;
+        self.document.footnotes = std::mem::take(&mut document.footnotes);
+        self.document.meta.extend(document.meta);
+        self.document.diagnostics.extend(document.diagnostics);
+        Ok(document.nodes)
+    }
+
+    /// The note runs to the end of the line and is kept aside, to be rendered with the
+    /// other notes at the end of the document.
+    fn parse_footnote_definition(&mut self) -> Result<(), ArcError> {
+        let token = self.consume()?;
+        let label = token
+            .value
+            .expect("Parser: FootnoteDefinition should contain a label");
+        let note = self.parse_line()?;
+        let span = token.span.to(&self.previous);
+        if let Err(warning) = self.document.footnotes.define(label, note, span) {
+            self.document.diagnostics.warn(warning);
+        }
+        Ok(())
+    }
+
+    fn parse_meta(&mut self) -> Result<(), ArcError> {
+        let token = self.consume()?;
+        let src = token
             .value
             .expect("Parser: MetaData should contain a value");
-        if let Some(meta) = MetaProperties::new(src) {
-            self.document.append_meta(meta);
+        match MetaProperties::new(src) {
+            Ok(meta) => self.document.append_meta(meta),
+            Err(err) => self.document.diagnostics.warn(err.at(token.span)),
         }
         Ok(())
     }
 
-    fn consume(&mut self) -> Result<Token, String> {
+    fn consume(&mut self) -> Result<Token, ArcError> {
         if self.at_eof() {
-            return Err("Parser: consume: at_eof".to_string());
+            return Err(ArcError::parser("Unexpected end of file").at(self.previous));
         }
-        Ok(self
+        let token = self
             .source
             .pop()
-            .expect("Parser: before EOF should always have a token to consume"))
+            .expect("Parser: before EOF should always have a token to consume");
+        self.previous = token.span;
+        Ok(token)
+    }
+
+    /// Math is inserted into the page verbatim for MathJax, so in safe mode it is
+    /// escaped first. MathJax reads the escaped text back unchanged.
+    fn escape_if_safe(&self, src: String) -> String {
+        match self.safe_mode {
+            true => html! { (src) }.into_string(),
+            false => src,
+        }
     }
 
     fn at_eof(&self) -> bool {
-        self.source.len() <= 1 || &self.source[self.source.len() - 1].kind == &TokenKind::EOF
+        self.source.len() <= 1 || self.source[self.source.len() - 1].kind == TokenKind::Eof
     }
 
     fn at_end_of_line(&self) -> bool {
-        &self.source[self.source.len() - 1].kind == &TokenKind::EndOfLine
+        self.source[self.source.len() - 1].kind == TokenKind::EndOfLine
     }
 
     fn this_kind(&self) -> &TokenKind {
         &self.source[self.source.len() - 1].kind
     }
 
-    fn expect(&mut self, kind: TokenKind) -> Result<Token, String> {
-        let token = self.consume()?;
-        if token.kind != kind {
-            return Err(format!("Parser: expected {:?}, got {:?}", kind, token.kind));
-        }
-        Ok(token)
+    fn this_span(&self) -> Span {
+        self.source.last().map_or(self.previous, |token| token.span)
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, ArcError> {
+        let found = if self.at_eof() {
+            String::from("end of file")
+        } else if self.this_kind() != &kind {
+            format!("{:?}", self.this_kind())
+        } else {
+            return self.consume();
+        };
+        let error =
+            ArcError::parser(format!("Expected {:?}, got {}", kind, found)).at(self.this_span());
+        Err(match kind {
+            TokenKind::RightParenthesis => error
+                .with_help("close the group with `)`, or write `\\)` for a literal parenthesis"),
+            _ => error,
+        })
     }
 }
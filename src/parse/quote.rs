@@ -0,0 +1,63 @@
+use super::node::ASTNode;
+use crate::lexer::span::Span;
+use std::collections::VecDeque;
+
+/// Turns a run of quote lines, in source order, into one quote nested by depth. A line
+/// with more `>` markers than the one before it opens a quote inside the current one,
+/// and the last `> --` line at a depth becomes that quote's attribution.
+pub fn nest_quotes(lines: Vec<ASTNode>) -> ASTNode {
+    collect_quote(&mut lines.into(), 1)
+}
+
+fn collect_quote(lines: &mut VecDeque<ASTNode>, depth: usize) -> ASTNode {
+    let mut content: Vec<ASTNode> = Vec::new();
+    let mut attribution: Option<Box<ASTNode>> = None;
+    let mut spans: Vec<Span> = Vec::new();
+
+    while let Some(next) = lines.front() {
+        let next_depth = match next {
+            ASTNode::QuoteLine { depth, .. } => *depth,
+            _ => 0,
+        };
+        if next_depth < depth {
+            break;
+        }
+        if next_depth > depth {
+            let quote = collect_quote(lines, depth + 1);
+            spans.push(quote.span());
+            content.push(quote);
+            continue;
+        }
+        let Some(ASTNode::QuoteLine {
+            syntax,
+            content: line,
+            attribution: is_attribution,
+            span,
+            ..
+        }) = lines.pop_front()
+        else {
+            unreachable!("Checked front item is a quote line.");
+        };
+        spans.push(span);
+        let line = ASTNode::Inline {
+            syntax,
+            content: line,
+            span,
+        };
+        if is_attribution {
+            attribution = Some(Box::new(line));
+        } else {
+            content.push(line);
+        }
+    }
+
+    let span = match (spans.first(), spans.last()) {
+        (Some(first), Some(last)) => first.to(last),
+        _ => Span::default(),
+    };
+    ASTNode::BlockQuote {
+        content,
+        attribution,
+        span,
+    }
+}
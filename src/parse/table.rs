@@ -1,30 +1,52 @@
 use super::node::{ASTNode, BlockedContent, TableContent};
 use super::tree::Document;
 use crate::lexer::lexer_lite::LexerLite;
+use crate::lexer::span::{SourceText, Span};
 use crate::lexer::traits::LexerTrait;
-use crate::utilities::constants::{MULTIPLE_NEWLINE_REGEX, WIDTH_HEIGHT_REGEX};
-use fancy_regex::Regex;
+use crate::utilities::deadline::Deadline;
+use crate::utilities::error::ArcError;
+use crate::utilities::regexes;
 
-pub fn parse_table(src: String, document: &mut Document) -> Result<(), String> {
-    let regex = Regex::new(MULTIPLE_NEWLINE_REGEX).expect("Hard coded regex should be valid.");
-    let src = regex.replace_all(&src, "\n").to_string();
-    let mut src: Vec<&str> = src.split("\n").collect();
+/// `body` is the table's rows as preprocessed source, to point errors at the row they
+/// are in; without it they point at the whole table.
+pub fn parse_table(
+    src: String,
+    span: Span,
+    body: Option<SourceText>,
+    document: &mut Document,
+    deadline: Deadline,
+    safe_mode: bool,
+) -> Result<(), ArcError> {
+    let mut offset = 0;
+    let mut src: Vec<(&str, Span)> = src
+        .split("\n")
+        .map(|line| {
+            let start = offset;
+            offset += line.len() + 1;
+            let span = body
+                .as_ref()
+                .map_or(span, |body| body.span(start, start + line.len()));
+            (line, span)
+        })
+        .collect();
     if src.len() == 0 {
-        crate::warn!("Runtime Warning: Invalid table syntax: Empty table");
+        document
+            .diagnostics
+            .warn(ArcError::table("Invalid table syntax: Empty table").at(span));
         return Ok(());
     }
 
     let mut table_content: Vec<Vec<TableContent>> = Vec::new();
 
-    let first_line = src[0].trim();
+    let first_line = src[0].0.trim();
     let position = parse_position(first_line);
 
     if position != (None, None) {
         src.remove(0);
     }
 
-    let mut row_pos = 0;
-    'outer: for line in src {
+    let mut row_pos: usize = 0;
+    'outer: for (line, row_span) in src {
         let mut line = line.trim();
         if line.is_empty() {
             continue 'outer;
@@ -35,38 +57,57 @@ pub fn parse_table(src: String, document: &mut Document) -> Result<(), String> {
         }
         table_content.push(Vec::new());
 
-        let mut col_pos = 0;
-        'inner: for cell in line.split(";") {
+        let mut col_pos: usize = 0;
+        'inner: for cell in split_cells(line) {
             let (content, style) = format_style(cell);
 
             if content.trim() == "_" {
-                table_content[row_pos]
-                    .get_mut(col_pos - 1)
-                    .expect("Row merge with no left neighbor")
+                col_pos
+                    .checked_sub(1)
+                    .and_then(|col| table_content[row_pos].get_mut(col))
+                    .ok_or_else(|| {
+                        ArcError::table("Row merge `_` with no left neighbor")
+                            .at(row_span)
+                            .with_help("`_` merges a cell into the cell on its left")
+                    })?
                     .add_merge_col();
                 // row_pos += 1;
                 continue 'inner;
             }
             if content.trim() == "^" {
-                table_content
-                    .get_mut(row_pos - 1)
-                    .expect("Column merge with no upper row")
-                    .get_mut(col_pos)
-                    .expect("Column merge with no upper neighbor")
+                row_pos
+                    .checked_sub(1)
+                    .and_then(|row| table_content.get_mut(row))
+                    .and_then(|row| row.get_mut(col_pos))
+                    .ok_or_else(|| {
+                        ArcError::table("Column merge `^` with no upper neighbor")
+                            .at(row_span)
+                            .with_help("`^` merges a cell into the cell above it")
+                    })?
                     .add_merge_row();
                 col_pos += 1;
                 continue 'inner;
             }
 
-            let lexer = LexerLite::new(content);
+            let lexer = LexerLite::new(content).with_base(span);
             let tokens = lexer.tokenize()?;
-            let parser = super::parse::Parser::new(tokens);
-            let mut nodes = parser.parse()?.nodes;
+            let parser = super::parse::Parser::new(tokens)
+                .with_footnotes(std::mem::take(&mut document.footnotes))
+                .with_deadline(deadline)
+                .with_safe_mode(safe_mode);
+            let mut cell = parser.parse_nested()?;
+            document.footnotes = std::mem::take(&mut cell.footnotes);
+            if let Some(error) = cell.diagnostics.first_error() {
+                return Err(error);
+            }
+            document.diagnostics.extend(cell.diagnostics);
+            let mut nodes = cell.nodes;
             if nodes.len() == 0 {
                 // info: empty cell, composed with ;; ...
                 table_content[row_pos].push(TableContent::new(
                     vec![ASTNode::BlockedContent {
                         content: BlockedContent::PlainText(String::new()),
+                        span,
                     }],
                     is_heading,
                     style,
@@ -86,20 +127,43 @@ pub fn parse_table(src: String, document: &mut Document) -> Result<(), String> {
     document.append_node(vec![ASTNode::Table {
         position,
         content: table_content,
+        span,
     }]);
     Ok(())
 }
 
-fn parse_position(line: &str) -> (Option<f32>, Option<f32>) {
-    let regex = Regex::new(WIDTH_HEIGHT_REGEX).expect("Hard coded regex should be valid.");
-    let matched = regex.find(line);
-    if let Ok(Some(_)) = matched {
-        let captures = regex.captures(line).unwrap().unwrap();
-        let width = captures.get(1).unwrap().as_str().parse::<f32>().unwrap();
-        let height = captures.get(2).unwrap().as_str().parse::<f32>().unwrap();
-        return (Some(width), Some(height));
+pub fn parse_position(line: &str) -> (Option<f32>, Option<f32>) {
+    let Ok(Some(captures)) = regexes::WIDTH_HEIGHT.captures(line) else {
+        return (None, None);
+    };
+    let number = |index| {
+        captures
+            .get(index)
+            .and_then(|m| m.as_str().parse::<f32>().ok())
+    };
+    match (number(1), number(2)) {
+        (Some(width), Some(height)) => (Some(width), Some(height)),
+        _ => (None, None),
+    }
+}
+
+/// Splits a row on `;`, except inside inline code.
+fn split_cells(line: &str) -> Vec<&str> {
+    let mut cells = Vec::new();
+    let mut in_code = false;
+    let mut start = 0;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '`' if in_code || line[index + 1..].contains('`') => in_code = !in_code,
+            ';' if !in_code => {
+                cells.push(&line[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
     }
-    (None, None)
+    cells.push(&line[start..]);
+    cells
 }
 
 fn is_heading(line: &str) -> bool {
@@ -108,7 +172,7 @@ fn is_heading(line: &str) -> bool {
 
 fn format_style(line: &str) -> (String, String) {
     let line = line.trim();
-    if line.starts_with("=") && line.ends_with("=") {
+    if line.len() > 1 && line.starts_with("=") && line.ends_with("=") {
         return (
             String::from(&line[1..line.len() - 1]),
             String::from("text-align: center;"),
@@ -0,0 +1,84 @@
+use super::node::{ASTNode, StyledSyntax, TocEntry};
+use std::collections::HashSet;
+
+/// Headings deeper than this are left out of a table of contents unless the document
+/// sets `toc-depth`.
+pub const DEFAULT_DEPTH: u8 = 3;
+
+/// Gives every heading, including those in callouts and details sections, an anchor
+/// made from its text, and returns the document's own headings, the ones outside
+/// containers, in order. Repeated slugs get `-1`, `-2`, ... appended, so an anchor
+/// only changes when its heading, or an earlier one with the same text, does.
+pub fn anchor_headings(nodes: &mut [Vec<ASTNode>]) -> Vec<TocEntry> {
+    let mut used = HashSet::new();
+    let mut entries = Vec::new();
+    for node in nodes.iter_mut().flatten() {
+        if let Some(entry) = anchor(node, &mut used) {
+            entries.push(entry);
+        }
+        if let ASTNode::Callout { content, .. } | ASTNode::Details { content, .. } = node {
+            for node in content.iter_mut().flatten() {
+                anchor_nested(node, &mut used);
+            }
+        }
+    }
+    entries
+}
+
+fn anchor_nested(node: &mut ASTNode, used: &mut HashSet<String>) {
+    anchor(node, used);
+    if let ASTNode::Callout { content, .. } | ASTNode::Details { content, .. } = node {
+        for node in content.iter_mut().flatten() {
+            anchor_nested(node, used);
+        }
+    }
+}
+
+fn anchor(node: &mut ASTNode, used: &mut HashSet<String>) -> Option<TocEntry> {
+    let ASTNode::Inline { syntax, .. } = node else {
+        return None;
+    };
+    let level = syntax.iter().find_map(|s| match s {
+        StyledSyntax::Heading(level) => Some(*level),
+        _ => None,
+    })?;
+    let text = node.text();
+    let slug = slugify(&text);
+    let mut anchor = slug.clone();
+    let mut suffix = 0;
+    while !used.insert(anchor.clone()) {
+        suffix += 1;
+        anchor = format!("{}-{}", slug, suffix);
+    }
+    if let ASTNode::Inline { syntax, .. } = node {
+        syntax.push(StyledSyntax::Anchor(anchor.clone()));
+    }
+    Some(TocEntry {
+        level,
+        anchor,
+        text: text.trim().to_string(),
+    })
+}
+
+/// Lowercases `text` and joins its words with `-`, dropping punctuation, e.g.
+/// `"2. Proof: Part B"` becomes `"2-proof-part-b"`.
+pub fn slugify(text: &str) -> String {
+    let slug = text
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() => Some(c.to_lowercase().to_string()),
+            ' ' | '\t' | '-' | '_' => Some(String::from("-")),
+            _ => None,
+        })
+        .collect::<String>();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+    if slug.is_empty() {
+        String::from("section")
+    } else {
+        slug
+    }
+}
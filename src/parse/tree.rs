@@ -1,50 +1,185 @@
+use super::footnote::Footnotes;
 use super::meta::MetaProperties;
 use super::node::ASTNode;
-use crate::utilities::constants::ANTI_META_REGEX;
-use crate::utilities::style::STYLE;
-use fancy_regex::Regex;
+use super::toc::{DEFAULT_DEPTH, anchor_headings};
+use crate::lexer::span::Span;
+use crate::utilities::diagnostics::Diagnostics;
+use crate::utilities::regexes;
+use crate::utilities::style::Theme;
 use maud::{DOCTYPE, PreEscaped, html};
 
+/// How many `- [ ]` and `- [x]` items a document has.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TaskCount {
+    pub open: usize,
+    pub done: usize,
+}
+
 #[derive(Debug)]
 pub struct Document {
-    pub meta: Vec<MetaProperties>,
-    pub nodes: Vec<Vec<ASTNode>>,
+    pub(crate) meta: Vec<MetaProperties>,
+    pub(crate) nodes: Vec<Vec<ASTNode>>,
+    pub(crate) footnotes: Footnotes,
+    pub(crate) diagnostics: Diagnostics,
 }
 
 impl Document {
-    pub fn init() -> Self {
+    pub(crate) fn init() -> Self {
         Self {
             meta: Vec::new(),
             nodes: Vec::new(),
+            footnotes: Footnotes::default(),
+            diagnostics: Diagnostics::new(),
         }
     }
 
-    pub fn append_meta(&mut self, meta: MetaProperties) {
+    pub(crate) fn append_meta(&mut self, meta: MetaProperties) {
         self.meta.push(meta);
     }
 
-    pub fn append_node(&mut self, node: Vec<ASTNode>) {
+    pub(crate) fn append_node(&mut self, node: Vec<ASTNode>) {
         self.nodes.push(node);
     }
 
+    /// Errors the compiler recovered from, and warnings.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// The output file name set with `<meta name=... />`, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.meta.iter().find_map(|meta| match meta {
+            MetaProperties::Name(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Counts the task list items in the document, including nested ones.
+    pub fn tasks(&self) -> TaskCount {
+        let mut count = TaskCount::default();
+        self.nodes
+            .iter()
+            .flatten()
+            .for_each(|node| Self::count_tasks(node, &mut count));
+        count
+    }
+
+    fn count_tasks(node: &ASTNode, count: &mut TaskCount) {
+        match node {
+            ASTNode::List { items, .. } => {
+                items.iter().for_each(|item| Self::count_tasks(item, count));
+            }
+            ASTNode::Callout { content, .. } | ASTNode::Details { content, .. } => {
+                content
+                    .iter()
+                    .flatten()
+                    .for_each(|node| Self::count_tasks(node, count));
+            }
+            ASTNode::ListItem { task, children, .. } => {
+                match task {
+                    Some(true) => count.done += 1,
+                    Some(false) => count.open += 1,
+                    None => {}
+                }
+                children
+                    .iter()
+                    .for_each(|child| Self::count_tasks(child, count));
+            }
+            _ => {}
+        }
+    }
+
+    /// Anchors every heading and fills in the `<toc />` placeholders. With
+    /// `<meta toc=true />` and no placeholder, a table of contents opens the document.
+    pub(crate) fn outline(&mut self) {
+        let entries = anchor_headings(&mut self.nodes);
+        let depth = self
+            .meta
+            .iter()
+            .find_map(|meta| match meta {
+                MetaProperties::TocDepth(depth) => Some(*depth),
+                _ => None,
+            })
+            .unwrap_or(DEFAULT_DEPTH);
+        let mut placed = false;
+        for node in self.nodes.iter_mut().flatten() {
+            if let ASTNode::TableOfContents {
+                depth: own,
+                entries: listed,
+                ..
+            } = node
+            {
+                *own = own.or(Some(depth));
+                *listed = entries.clone();
+                placed = true;
+            }
+        }
+        if !placed && self.meta.contains(&MetaProperties::Toc(true)) {
+            self.nodes.insert(
+                0,
+                vec![ASTNode::TableOfContents {
+                    depth: Some(depth),
+                    entries,
+                    span: Span::default(),
+                }],
+            );
+        }
+    }
+
+    /// Copies each footnote next to its first reference when the document asks for
+    /// sidenotes with `<meta footnotes=sidenotes />`.
+    pub(crate) fn place_sidenotes(&mut self) {
+        if self.meta.contains(&MetaProperties::Sidenotes(true)) {
+            for node in self.nodes.iter_mut().flatten() {
+                self.footnotes.place_sidenotes(node);
+            }
+        }
+    }
+
+    /// Opens every `--- details!` section into a regular section, for PDF builds where
+    /// nothing can be clicked. `<meta pdf-details=collapsed />` keeps them collapsed.
+    pub fn expand_details_for_pdf(&mut self) {
+        let collapsed = self
+            .meta
+            .iter()
+            .any(|meta| meta == &MetaProperties::PdfDetails(false));
+        if !collapsed {
+            self.nodes
+                .iter_mut()
+                .flatten()
+                .for_each(Self::expand_details);
+        }
+    }
+
+    fn expand_details(node: &mut ASTNode) {
+        match node {
+            ASTNode::Details {
+                content, expanded, ..
+            } => {
+                *expanded = true;
+                content.iter_mut().flatten().for_each(Self::expand_details);
+            }
+            ASTNode::Callout { content, .. } => {
+                content.iter_mut().flatten().for_each(Self::expand_details);
+            }
+            _ => {}
+        }
+    }
+
     pub fn build(&self) -> String {
+        self.build_with_theme(&Theme::Default)
+    }
+
+    pub fn build_with_theme(&self, theme: &Theme) -> String {
         let meta = self
             .meta
             .iter()
             .map(|m| m.build())
             .collect::<Vec<String>>()
             .join("");
-        let nodes = self
-            .nodes
-            .iter()
-            .map(|n| {
-                n.iter()
-                    .map(|n| n.build().into_string())
-                    .collect::<Vec<String>>()
-                    .join("")
-            })
-            .collect::<Vec<String>>()
-            .join("<br />");
+        let nodes = ASTNode::build_lines(&self.nodes);
+        let sidenotes = self.meta.contains(&MetaProperties::Sidenotes(true));
+        let footnotes = self.footnotes.build(sidenotes);
         let src = html!(
             (DOCTYPE)
             html lang=(PreEscaped("en")) {
@@ -60,10 +195,11 @@ impl Document {
                     script src=(PreEscaped("https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js")) {};
                     script src=(PreEscaped("https://cdn.jsdelivr.net/npm/prismjs@1.29.0/prism.min.js")) {};
                     script src=(PreEscaped("https://cdn.jsdelivr.net/npm/prismjs@1.29.0/plugins/autoloader/prism-autoloader.min.js")) {};
-                    style { (PreEscaped(STYLE)) }
+                    style { (PreEscaped(theme.css())) }
                 }
                 body {
                     (PreEscaped(nodes))
+                    (footnotes)
                 }
             }
         )
@@ -74,13 +210,12 @@ impl Document {
     }
 
     fn fix_leading_br(src: String) -> String {
-        let regex = Regex::new(ANTI_META_REGEX).expect("Hard coded regex should be valid.");
-        regex.replace_all(&src, "<body>").to_string()
+        regexes::ANTI_META.replace_all(&src, "<body>").to_string()
     }
 
     fn replace_redundancy(src: String) -> String {
-        src.replace(r#"class="""#, "")
-            .replace(r#"style="""#, "")
+        src.replace(r#" class="""#, "")
+            .replace(r#" style="""#, "")
             .replace("</ol><br />", "</ol>")
             .replace("</ul><br />", "</ul>")
             .replace("</table><br /><br />", "</table>")
@@ -103,22 +238,24 @@ impl Document {
             let ch = chars[i];
             let prev_char = if i > 0 { chars[i - 1] } else { '\0' };
 
-            // Check for opening style or script tags
+            // Check for opening style, script or code tags
             if ch == '<' && !inside_quote && !inside_style_or_script {
-                // Look ahead to see if this is a style or script tag
+                // Look ahead to see if this is a style, script or code tag
                 let remaining: String = chars[i..].iter().collect();
                 if remaining.to_lowercase().starts_with("<style")
                     || remaining.to_lowercase().starts_with("<script")
+                    || remaining.to_lowercase().starts_with("<code")
                 {
                     inside_style_or_script = true;
                 }
                 inside_tag = true;
             }
-            // Check for closing style or script tags
+            // Check for closing style, script or code tags
             else if ch == '<' && !inside_quote && inside_style_or_script {
                 let remaining: String = chars[i..].iter().collect();
                 if remaining.to_lowercase().starts_with("</style>")
                     || remaining.to_lowercase().starts_with("</script>")
+                    || remaining.to_lowercase().starts_with("</code>")
                 {
                     inside_style_or_script = false;
                 }
@@ -138,7 +275,7 @@ impl Document {
                 }
             }
 
-            // Replace space only if we're not inside tags, quotes, or style/script blocks
+            // Replace space only if we're not inside tags, quotes, or style/script/code blocks
             if ch == ' ' && !inside_tag && !inside_quote && !inside_style_or_script {
                 result.push_str("&nbsp;");
             } else {
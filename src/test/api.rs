@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use crate::{ErrorKind, Options, Theme, compile, render};
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn test_render_default_options() {
+    let html = render("Hello, **world**!", &Options::default()).unwrap();
+    assert!(html.contains("<span>world</span></strong>"));
+    assert!(html.contains("--text-color"));
+}
+
+#[test]
+fn test_render_custom_theme() {
+    let options = Options {
+        theme: Theme::Custom(String::from("body { color: red; }")),
+        ..Options::default()
+    };
+    let html = render("Hello", &options).unwrap();
+    assert!(html.contains("body { color: red; }"));
+    assert!(!html.contains("--text-color"));
+}
+
+#[test]
+fn test_compile_keeps_recovered_errors() {
+    let document = compile("first)\nsecond", &Options::default()).unwrap();
+    assert_eq!(document.diagnostics.errors().count(), 1);
+    assert!(render("first)\nsecond", &Options::default()).is_err());
+}
+
+#[test]
+fn test_compile_timeout() {
+    let options = Options {
+        timeout: Some(Duration::ZERO),
+        ..Options::default()
+    };
+    let error = compile("Hello", &options).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Timeout);
+}
+
+#[test]
+fn test_stdlib_path() {
+    let dir = std::env::temp_dir().join("arc_api_stdlib");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("greet.txt"),
+        "<script>\nfn $hi(): Hi there\n</script>",
+    )
+    .unwrap();
+    let options = Options {
+        stdlib_path: dir.clone(),
+        ..Options::default()
+    };
+    let src = "<script>\n@include <std/greet>\n</script>\n$hi()";
+    let html = render(src, &options).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(html.contains("Hi&nbsp;there"));
+}
+
+#[test]
+fn test_safe_mode() {
+    let options = Options {
+        safe_mode: true,
+        ..Options::default()
+    };
+    let src = "<script>\n@include </etc/passwd>\n</script>\n--- html!\n<b>raw</b>\n---\n<math <img src=x> />";
+    let document = compile(src, &options).unwrap();
+    assert_eq!(document.diagnostics.warnings().count(), 2);
+    let html = document.build();
+    assert!(!html.contains("<b>raw</b>"));
+    assert!(html.contains("&lt;b&gt;raw&lt;/b&gt;"));
+    assert!(!html.contains("<img"));
+}
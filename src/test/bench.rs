@@ -0,0 +1,67 @@
+#![cfg(test)]
+
+// Run with `cargo test --release -- --ignored bench --nocapture`.
+
+use crate::lexer::lexer::Lexer;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use std::time::{Duration, Instant};
+
+const SECTION: &str = r"# Lecture notes
+Plain text with **bold words**, ~italic text and a &[https://example.com] link.
+%[red:14]Styled line with <math x^2 + y^2 /> inline math and \(%[blue]nested) groups.
+- first item
+- second item with @[term] 'its definition'
+1. ordered item
+2. another ordered item
+---
+<code>:rust
+fn main() {}
+</code>
+
+";
+
+fn document(size: usize) -> String {
+    SECTION.repeat(size / SECTION.len() + 1)
+}
+
+fn time_tokenize(source: String) -> Duration {
+    let start = Instant::now();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let elapsed = start.elapsed();
+    assert!(tokens.len() > 1);
+    elapsed
+}
+
+#[test]
+#[ignore]
+fn bench_lexer_scales_linearly() {
+    let small = time_tokenize(document(256 * 1024));
+    let large = time_tokenize(document(1024 * 1024));
+    println!("256 KB: {:?}, 1 MB: {:?}", small, large);
+
+    // Four times the input should take about four times as long; a quadratic lexer
+    // would take about sixteen times as long.
+    assert!(large < small * 8, "256 KB: {:?}, 1 MB: {:?}", small, large);
+}
+
+#[test]
+#[ignore]
+fn bench_large_table() {
+    let mut source = String::from("--- table!\n");
+    for row in 0..2000 {
+        source.push_str(&format!(
+            "Cell {row};**bold**;~italic;&[https://example.com]\n"
+        ));
+    }
+    source.push_str("---\n");
+
+    let start = Instant::now();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let document = Parser::new(tokens).parse().unwrap();
+    let elapsed = start.elapsed();
+    println!("2000 rows, 8000 cells: {:?}", elapsed);
+
+    assert!(document.diagnostics.is_empty());
+    assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+}
@@ -0,0 +1,191 @@
+#![cfg(test)]
+
+use super::{body, body_of, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::span::Span;
+use crate::lexer::token::{Token, TokenKind};
+use crate::lexer::traits::LexerTrait;
+use crate::parse::node::{ASTNode, CalloutKind};
+use crate::parse::parse::Parser;
+use crate::parse::tree::TaskCount;
+use crate::{Options, compile};
+
+#[test]
+fn test_callout_token() {
+    let tokens = Lexer::new(String::from("--- note! Heads up\nbody\n---"))
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Callout);
+    assert_eq!(tokens[0].value.as_deref(), Some("--- note! Heads up\nbody"));
+}
+
+#[test]
+fn test_kinds_and_titles() {
+    for (src, kind, title) in [
+        ("--- note!\nx\n---", CalloutKind::Note, None),
+        (
+            "---warning! Careful\nx\n---",
+            CalloutKind::Warning,
+            Some("Careful"),
+        ),
+        ("--- tip!   \nx\n---", CalloutKind::Tip, None),
+        (
+            "--- danger! Do not\nx\n---",
+            CalloutKind::Danger,
+            Some("Do not"),
+        ),
+    ] {
+        match &parse(src).nodes[0][0] {
+            ASTNode::Callout {
+                kind: k, title: t, ..
+            } => {
+                assert_eq!(*k, kind, "{}", src);
+                assert_eq!(t.as_deref(), title, "{}", src);
+            }
+            other => panic!("expected a callout, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_render() {
+    let html = body("--- note!\nhello\n---\nafter");
+    assert!(html.contains(
+        r#"<div class="callout callout-note"><div class="callout-title">Note</div><div class="callout-body"><span><span>hello</span></span></div></div><span><span>after</span></span>"#
+    ));
+}
+
+#[test]
+fn test_body_is_a_full_document() {
+    let src = "--- tip! Workflow\n# Steps\n1. build\n2. test\n> quoted\n- [ ] ship\n---";
+    let document = parse(src);
+    let ASTNode::Callout { content, .. } = &document.nodes[0][0] else {
+        panic!("expected a callout");
+    };
+    let nodes = content.iter().flatten().collect::<Vec<_>>();
+    assert!(matches!(nodes[0], ASTNode::Inline { .. }));
+    assert!(matches!(nodes[1], ASTNode::List { .. }));
+    assert!(matches!(nodes[2], ASTNode::BlockQuote { .. }));
+    assert!(matches!(nodes[3], ASTNode::List { .. }));
+    assert_eq!(document.tasks(), TaskCount { open: 1, done: 0 });
+
+    let html = body(src);
+    assert!(html.contains("<span>Steps</span></h1><ol>"));
+}
+
+#[test]
+fn test_empty_callout() {
+    let html = body("--- danger!\n---");
+    assert!(html.contains(r#"<div class="callout-body"></div>"#));
+}
+
+#[test]
+fn test_html_container_is_unchanged() {
+    let html = body("--- html!\n<b>raw</b>\n---");
+    assert!(html.contains("<b>raw</b>"));
+    assert!(!html.contains("callout"));
+}
+
+#[test]
+fn test_html_container_keeps_its_line_break() {
+    for tag in ["div", "section", "nav", "blockquote", "details", "h2"] {
+        let src = format!("--- html!\n<{0}>raw</{0}>\n---\nafter", tag);
+        let html = body(&src);
+        assert!(
+            html.contains(&format!("<{0}>raw</{0}><br /><span><span>after", tag)),
+            "{}",
+            html
+        );
+    }
+}
+
+#[test]
+fn test_errors_point_into_the_body() {
+    let document = compile("intro\n--- note!\nfine\n)\n---", &Options::default()).unwrap();
+    let error = document.diagnostics.first_error().unwrap();
+    assert_eq!(error.message, "Unmatched `)`");
+    let span = error.span.unwrap();
+    assert_eq!(span.line, 4);
+    assert_eq!(span.column, 1);
+    assert_eq!(span.start, "intro\n--- note!\nfine\n".len());
+}
+
+#[test]
+fn test_errors_in_the_body_skip_comments() {
+    let source = "--- note!\n/// a\n/// b\nok\nbad)\n---";
+    let document = compile(source, &Options::default()).unwrap();
+    let error = document.diagnostics.first_error().unwrap();
+    assert_eq!(error.message, "Unmatched `)`");
+    let span = error.span.unwrap();
+    assert_eq!(span.line, 5);
+    assert_eq!(span.column, 4);
+    assert_eq!(span.start, source.find(')').unwrap());
+}
+
+#[test]
+fn test_malformed_header_is_an_error() {
+    for (kind, src, message) in [
+        (
+            TokenKind::Callout,
+            "--- aside! x\nbody",
+            "Unknown callout kind `aside`",
+        ),
+        (
+            TokenKind::Details,
+            "--- details\nbody",
+            "Container header is missing the `!` after its kind",
+        ),
+    ] {
+        let tokens = vec![
+            Token::new(kind, Some(src.to_string()), Span::default()),
+            Token::new(TokenKind::Eof, None, Span::default()),
+        ];
+        let document = Parser::new(tokens).parse_with_recovery().unwrap();
+        let error = document.diagnostics.first_error().unwrap();
+        assert_eq!(error.message, message);
+    }
+}
+
+#[test]
+fn test_safe_mode_applies_to_body() {
+    let options = Options {
+        safe_mode: true,
+        ..Options::default()
+    };
+    let html = compile("--- warning!\n<math>a<b</math>\n---", &options)
+        .unwrap()
+        .build();
+    assert!(html.contains("a&lt;b"));
+}
+
+#[test]
+fn test_table_inside_callout() {
+    let html = body("--- note!\n---table!\na; b\n---\nafter the table\n---\noutside");
+    let callout = &html[..html.find("outside").unwrap()];
+    assert!(callout.contains("<table"));
+    assert!(callout.contains("after&nbsp;the&nbsp;table</span></span></div></div>"));
+}
+
+#[test]
+fn test_nests_to_any_depth() {
+    let document = parse("--- note!\n--- details! S\n--- tip!\ndeep\n---\n---\nafter\n---");
+    assert_eq!(document.nodes.len(), 1);
+    let ASTNode::Callout { content, .. } = &document.nodes[0][0] else {
+        panic!("expected a callout");
+    };
+    let ASTNode::Details {
+        content: details, ..
+    } = &content[0][0]
+    else {
+        panic!("expected a details section");
+    };
+    assert!(matches!(
+        &details[0][0],
+        ASTNode::Callout {
+            kind: CalloutKind::Tip,
+            ..
+        }
+    ));
+    let html = body_of(&document);
+    assert!(html.contains("deep</span></span></div></div></div></details><span><span>after"));
+}
@@ -0,0 +1,60 @@
+#![cfg(test)]
+
+use super::body;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+
+#[test]
+fn test_inline_code_token() {
+    let tokens = Lexer::new("call `f(x)` now".to_string())
+        .tokenize()
+        .unwrap();
+    let kinds: Vec<&TokenKind> = tokens.iter().map(|token| &token.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            &TokenKind::String,
+            &TokenKind::InlineCode,
+            &TokenKind::String,
+            &TokenKind::Eof
+        ]
+    );
+    assert_eq!(tokens[1].value.as_deref(), Some("f(x)"));
+    assert_eq!(tokens[1].span.start, 5);
+    assert_eq!(tokens[1].span.end, 11);
+}
+
+#[test]
+fn test_inline_code_is_verbatim() {
+    let html = body("`**a** %[red] ~b <i>  c`");
+    assert!(html.contains("<code>**a** %[red] ~b &lt;i&gt;  c</code>"));
+}
+
+#[test]
+fn test_inline_code_skips_functions_and_comments() {
+    let html = body("<script>\nfn $up(*a): UP*a\n</script>\n$up(%1) `$up(%2)` `/// not a comment`");
+    assert!(html.contains("UP1"));
+    assert!(html.contains("<code>$up(%2)</code>"));
+    assert!(html.contains("<code>/// not a comment</code>"));
+}
+
+#[test]
+fn test_inline_code_in_group() {
+    let html = body("\\(see `g(y)`) after");
+    assert!(html.contains("<code>g(y)</code>"));
+    assert!(html.contains("after"));
+}
+
+#[test]
+fn test_inline_code_in_table_cell() {
+    let html = body("---\ntable!\n[Name;Value]\n`a;b`;c\n---");
+    assert!(html.contains("<code>a;b</code>"));
+    assert!(html.contains("<span>c</span>"));
+}
+
+#[test]
+fn test_lone_backtick_is_text() {
+    let html = body("it`s fine");
+    assert!(html.contains("it`s&nbsp;fine"));
+}
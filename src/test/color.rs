@@ -17,7 +17,10 @@ fn test_color_from_string_literal_2() {
 #[test]
 fn test_color_from_string_literal_3() {
     let color = Color::from_string("invalid".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid color literal: invalid");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Invalid color literal: invalid"
+    );
 }
 
 #[test]
@@ -30,7 +33,7 @@ fn test_color_from_string_rgb_1() {
 fn test_color_from_string_rgb_2() {
     let color = Color::from_string("(255, 0)".to_string());
     assert_eq!(
-        color.unwrap_err(),
+        color.unwrap_err().to_string(),
         "Insufficient values for rgb literal: (255, 0)"
     );
 }
@@ -39,7 +42,7 @@ fn test_color_from_string_rgb_2() {
 fn test_color_from_string_rgb_3() {
     let color = Color::from_string("(255, 0, 0, 0)".to_string());
     assert_eq!(
-        color.unwrap_err(),
+        color.unwrap_err().to_string(),
         "Too many values for rgb literal: (255, 0, 0, 0)"
     );
 }
@@ -47,13 +50,19 @@ fn test_color_from_string_rgb_3() {
 #[test]
 fn test_color_from_string_rgb_4() {
     let color = Color::from_string("(256, 0, 0)".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid value for rgb literal: 256");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Invalid value for rgb literal: 256"
+    );
 }
 
 #[test]
 fn test_color_from_string_rgb_5() {
     let color = Color::from_string("(-1, 0, 0)".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid value for rgb literal: -1");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Invalid value for rgb literal: -1"
+    );
 }
 
 #[test]
@@ -71,14 +80,17 @@ fn test_color_from_string_rgb_7() {
 #[test]
 fn test_color_from_string_rgb_8() {
     let color = Color::from_string("(a, b, c)".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid value for rgb literal: a");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Invalid value for rgb literal: a"
+    );
 }
 
 #[test]
 fn test_color_from_string_rgb_9() {
     let color = Color::from_string("()".to_string());
     assert_eq!(
-        color.unwrap_err(),
+        color.unwrap_err().to_string(),
         "Insufficient values for rgb literal: ()"
     );
 }
@@ -86,14 +98,17 @@ fn test_color_from_string_rgb_9() {
 #[test]
 fn test_color_from_string_rgb_10() {
     let color = Color::from_string("(,,,)".to_string());
-    assert_eq!(color.unwrap_err(), "Too many values for rgb literal: (,,,)");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Too many values for rgb literal: (,,,)"
+    );
 }
 
 #[test]
 fn test_color_from_string_rgb_11() {
     let color = Color::from_string("(255, 0, 0,)".to_string());
     assert_eq!(
-        color.unwrap_err(),
+        color.unwrap_err().to_string(),
         "Too many values for rgb literal: (255, 0, 0,)"
     );
 }
@@ -101,7 +116,10 @@ fn test_color_from_string_rgb_11() {
 #[test]
 fn test_color_from_string_rgb_12() {
     let color = Color::from_string("(,,)".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid value for rgb literal: ");
+    assert_eq!(
+        color.unwrap_err().to_string(),
+        "Invalid value for rgb literal: "
+    );
 }
 
 #[test]
@@ -119,5 +137,5 @@ fn test_color_from_string_literal_5() {
 #[test]
 fn test_color_from_string_literal_6() {
     let color = Color::from_string("".to_string());
-    assert_eq!(color.unwrap_err(), "Invalid color literal: ");
+    assert_eq!(color.unwrap_err().to_string(), "Invalid color literal: ");
 }
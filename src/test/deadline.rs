@@ -0,0 +1,48 @@
+#![cfg(test)]
+
+use crate::funcs::process::FunctionProcessor;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use crate::utilities::deadline::Deadline;
+use crate::utilities::error::ErrorKind;
+use std::time::Duration;
+
+fn expired() -> Deadline {
+    Deadline::after(Duration::ZERO)
+}
+
+#[test]
+fn test_no_limit_never_expires() {
+    assert!(Deadline::new(None).check().is_ok());
+    assert!(Deadline::new(Some(Duration::from_secs(60))).check().is_ok());
+}
+
+#[test]
+fn test_expired_deadline_stops_lexer() {
+    let result = Lexer::new(String::from("Hello\nworld"))
+        .with_deadline(expired())
+        .tokenize();
+    let error = result.unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Timeout);
+    assert_eq!(error.code(), "E0800");
+    assert!(error.help.is_some());
+}
+
+#[test]
+fn test_expired_deadline_stops_function_expansion() {
+    let content = "<script>\nfn $foo(): bar\n</script>\n$foo()";
+    let result = FunctionProcessor::new(content.to_string())
+        .with_deadline(expired())
+        .process();
+    assert_eq!(result.unwrap_err().kind, ErrorKind::Timeout);
+}
+
+#[test]
+fn test_expired_deadline_stops_parser() {
+    let tokens = Lexer::new(String::from("Hello\n--- table!\na;b\n---"))
+        .tokenize()
+        .unwrap();
+    let result = Parser::new(tokens).with_deadline(expired()).parse();
+    assert_eq!(result.unwrap_err().kind, ErrorKind::Timeout);
+}
@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use super::{body, body_of, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::node::ASTNode;
+
+#[test]
+fn test_details_token() {
+    let tokens = Lexer::new(String::from("--- details! Solution\nx = 2\n---"))
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Details);
+    assert_eq!(
+        tokens[0].value.as_deref(),
+        Some("--- details! Solution\nx = 2")
+    );
+}
+
+#[test]
+fn test_render_collapsed() {
+    let html = body("--- details! Solution\nx\n---\nafter");
+    assert!(html.contains(
+        r#"<details><summary>Solution</summary><div class="details-body"><span><span>x</span></span></div></details><span><span>after</span></span>"#
+    ));
+}
+
+#[test]
+fn test_default_summary() {
+    let html = body("--- details!\nx\n---");
+    assert!(html.contains("<summary>Details</summary>"));
+}
+
+#[test]
+fn test_body_is_a_full_document() {
+    let src = "--- details! Solution\n<math>x^2</math>\n<code>:rust\nfn main() {}\n</code>\n---table!\na; b\n---\n---";
+    let document = parse(src);
+    let ASTNode::Details { content, .. } = &document.nodes[0][0] else {
+        panic!("expected a details section");
+    };
+    assert!(
+        content
+            .iter()
+            .flatten()
+            .any(|node| matches!(node, ASTNode::Table { .. }))
+    );
+    let html = body_of(&document);
+    assert!(html.contains("$$x^2$$"));
+    assert!(html.contains("<td colspan=\"1\" rowspan=\"1\"><span><span>a</span></span></td>"));
+    assert!(html.contains(r#"<code class="language-rust">fn main() {}</code>"#));
+}
+
+#[test]
+fn test_expanded_for_pdf() {
+    let mut document = parse("--- details! Solution\nx\n---");
+    document.expand_details_for_pdf();
+    let html = body_of(&document);
+    assert!(html.contains(
+        r#"<section class="details"><div class="details-summary">Solution</div><div class="details-body"><span><span>x</span></span></div></section>"#
+    ));
+    assert!(!html.contains("<details>"));
+}
+
+#[test]
+fn test_meta_keeps_pdf_collapsed() {
+    let mut document = parse("<meta pdf-details=collapsed />\n--- details! Solution\nx\n---");
+    document.expand_details_for_pdf();
+    assert!(body_of(&document).contains("<details><summary>Solution</summary>"));
+}
+
+#[test]
+fn test_expanded_inside_callout() {
+    let mut document = parse("--- note!\n--- details! Hint\nx\n---\n---");
+    document.expand_details_for_pdf();
+    let ASTNode::Callout { content, .. } = &document.nodes[0][0] else {
+        panic!("expected a callout");
+    };
+    assert!(matches!(
+        &content[0][0],
+        ASTNode::Details { expanded: true, .. }
+    ));
+}
@@ -0,0 +1,77 @@
+#![cfg(test)]
+
+use crate::funcs::process::FunctionProcessor;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use crate::parse::tree::Document;
+use crate::utilities::error::{ArcError, ErrorKind, Severity};
+
+fn parse(source: &str) -> Document {
+    let (tokens, diagnostics) = Lexer::new(source.to_string())
+        .tokenize_with_diagnostics()
+        .unwrap();
+    Parser::new(tokens)
+        .with_diagnostics(diagnostics)
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_no_warnings() {
+    let document = parse("Hello, world!");
+    assert!(document.diagnostics.is_empty());
+}
+
+#[test]
+fn test_meta_warning_collected() {
+    let document = parse("<meta unknown=value />\nHello");
+    assert_eq!(document.diagnostics.iter().count(), 1);
+    let warning = document.diagnostics.iter().next().unwrap();
+    assert_eq!(warning.kind, ErrorKind::Meta);
+    assert_eq!(warning.severity, Severity::Warning);
+    assert_eq!(warning.span.unwrap().line, 1);
+}
+
+#[test]
+fn test_style_warning_collected() {
+    let document = parse("Hello\n%[notacolor]World");
+    let warning = document.diagnostics.iter().next().unwrap();
+    assert_eq!(warning.kind, ErrorKind::Style);
+    assert_eq!(warning.span.unwrap().line, 2);
+}
+
+#[test]
+fn test_function_warning_collected() {
+    let content = "<script>\nfn name(*a): *a\n</script>\nname(%x)".to_string();
+    let (_, diagnostics) = FunctionProcessor::new(content).process().unwrap();
+    let warning = diagnostics.iter().next().unwrap();
+    assert_eq!(warning.kind, ErrorKind::Function);
+    assert!(warning.message.contains("should start with `$`"));
+}
+
+#[test]
+fn test_import_warning_collected() {
+    let content = "<script>\n@include </tmp/nonexist.txt>\n</script>\n".to_string();
+    let (_, diagnostics) = FunctionProcessor::new(content).process().unwrap();
+    let warning = diagnostics.iter().next().unwrap();
+    assert!(warning.message.contains("Import path does not exist"));
+    assert_eq!(warning.span.unwrap().line, 1);
+}
+
+#[test]
+fn test_lexer_warnings_reach_document() {
+    let document = parse("<script>\n@include </tmp/nonexist.txt>\n</script>\nHello");
+    assert_eq!(document.diagnostics.iter().count(), 1);
+}
+
+#[test]
+fn test_warning_json() {
+    let warning = ArcError::meta("Unrecognized \"key\"")
+        .with_help("see syntax.md")
+        .warning();
+    assert_eq!(
+        warning.to_json(Some("doc.arc")),
+        r#"{"severity":"warning","code":"E0400","kind":"meta","message":"Unrecognized \"key\"","file":"doc.arc","help":"see syntax.md"}"#
+    );
+}
@@ -0,0 +1,125 @@
+#![cfg(test)]
+
+use super::body;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+
+#[test]
+fn test_emphasis_tokens() {
+    let cases = [
+        ("~~ gone", TokenKind::Strikethrough),
+        ("__ key", TokenKind::Underline),
+        ("^^2", TokenKind::Superscript),
+        (",,2", TokenKind::Subscript),
+        ("~ slanted", TokenKind::Italic),
+    ];
+    for (src, kind) in cases {
+        let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+        assert_eq!(tokens[0].kind, kind, "{}", src);
+        assert_eq!(tokens[1].kind, TokenKind::String, "{}", src);
+    }
+}
+
+#[test]
+fn test_emphasis_styles() {
+    assert!(body("~~ gone").contains(r#"style="text-decoration: line-through !important;""#));
+    assert!(body("__ key").contains(r#"style="text-decoration: underline !important;""#));
+    assert!(body("^^2").contains("vertical-align: super !important;"));
+    assert!(body(",,2").contains("vertical-align: sub !important;"));
+}
+
+#[test]
+fn test_decorations_combine() {
+    let html = body("~~ __ both");
+    assert!(html.contains("text-decoration: line-through underline !important;"));
+}
+
+#[test]
+fn test_emphasis_with_character_style() {
+    let html = body("%[red] __ warning");
+    assert!(html.contains("color: rgb(255, 0, 0) !important;"));
+    assert!(html.contains("text-decoration: underline !important;"));
+}
+
+#[test]
+fn test_emphasis_in_group() {
+    let html = body("H\\(,,2)O and E = mc\\(^^2)");
+    assert!(html.contains(
+        "vertical-align: sub !important;font-size: smaller !important;\"><span>2</span>"
+    ));
+    assert!(html.contains(
+        "vertical-align: super !important;font-size: smaller !important;\"><span>2</span>"
+    ));
+}
+
+#[test]
+fn test_markers_inside_text_are_plain() {
+    let html = body("snake__case and a,,b");
+    assert!(html.contains("snake__case&nbsp;and&nbsp;a,,b"));
+}
+
+#[test]
+fn test_bold_holds_inline_content() {
+    let html = body("**&[https://example.com] docs** and **area <math r^2 />**");
+    assert!(
+        html.contains("<strong><a href=\"https://example.com\"><span>docs</span></a></strong>")
+    );
+    assert!(html.contains("<strong><span>area&nbsp;</span><span>\\(r^2\\)</span></strong>"));
+}
+
+#[test]
+fn test_bold_with_styles() {
+    let html = body("a **%[red] ~ warm** b");
+    assert!(html.contains(
+        "<strong style=\"color: rgb(255, 0, 0) !important;font-style: italic !important;\"><span>warm</span></strong>"
+    ));
+    let html = body("**x \\(__ y) `**`**");
+    assert!(html.contains("text-decoration: underline !important;\"><span>y</span></span>"));
+    assert!(html.contains("<code>**</code></strong>"));
+}
+
+#[test]
+fn test_bold_with_link_and_style_mid_line() {
+    let html = body("**a &[https://x.com] b %[red] word**");
+    assert!(html.contains(concat!(
+        r#"<strong><span>a&nbsp;</span><a href="https://x.com"><span>b&nbsp;</span></a>"#,
+        r#"<span style="color: rgb(255, 0, 0) !important;"><span>word</span></span></strong>"#,
+    )));
+}
+
+#[test]
+fn test_style_mid_line_ends_with_group() {
+    let html = body("a \\(b %[red] c) d");
+    assert!(html.contains(
+        r#"<span><span>b&nbsp;</span><span style="color: rgb(255, 0, 0) !important;"><span>c</span></span></span><span>&nbsp;d</span>"#
+    ));
+}
+
+#[test]
+fn test_bold_unmatched_parenthesis() {
+    let tokens = Lexer::new("**f(x)**".to_string()).tokenize().unwrap();
+    let error = Parser::new(tokens).parse().unwrap_err();
+    assert!(error.message.contains("Unmatched `)`"));
+    assert!(body("**f(x\\)**").contains("<span>f(x</span><span>)</span></strong>"));
+}
+
+#[test]
+fn test_stray_markers_are_plain() {
+    for (src, text) in [
+        (
+            "see <math x />,, and",
+            "<span>,,&nbsp;</span><span>and</span>",
+        ),
+        ("call `f`__ now", "<span>__&nbsp;</span><span>now</span>"),
+        ("x[^1]^^2", "<span>^^</span><span>2</span>"),
+        (
+            "&[https://x.com] __init__",
+            "<span>__</span><span>init__</span>",
+        ),
+    ] {
+        let html = body(src);
+        assert!(html.contains(text), "{}: {}", src, html);
+    }
+}
@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use crate::funcs::process::FunctionProcessor;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::span::Span;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use crate::parse::tree::Document;
+use crate::utilities::error::{ArcError, ErrorKind, Report, Severity};
+
+fn parse(source: &str) -> Result<Document, ArcError> {
+    let tokens = Lexer::new(source.to_string()).tokenize()?;
+    Parser::new(tokens).parse()
+}
+
+#[test]
+fn test_error_codes() {
+    assert_eq!(ArcError::lexer("").code(), "E0100");
+    assert_eq!(ArcError::parser("").code(), "E0200");
+    assert_eq!(ArcError::function("").code(), "E0300");
+    assert_eq!(ArcError::meta("").code(), "E0400");
+    assert_eq!(ArcError::style("").code(), "E0500");
+    assert_eq!(ArcError::table("").code(), "E0600");
+    assert_eq!(ArcError::io("").code(), "E0700");
+}
+
+#[test]
+fn test_error_keeps_first_span() {
+    let error = ArcError::parser("msg")
+        .at(Span::new(1, 2, 1, 2))
+        .at(Span::new(5, 6, 2, 1));
+    assert_eq!(error.span, Some(Span::new(1, 2, 1, 2)));
+    assert_eq!(error.severity, Severity::Error);
+    assert_eq!(error.warning().severity, Severity::Warning);
+}
+
+#[test]
+fn test_parser_error_span() {
+    let error = parse("first line\n\\(unclosed group").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Parser);
+    let span = error.span.unwrap();
+    assert_eq!(span.line, 2);
+    assert!(error.help.is_some());
+}
+
+#[test]
+fn test_function_error_span() {
+    let source = "text\n<script>\nnot a function\n</script>\n".to_string();
+    let error = FunctionProcessor::new(source).process().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Function);
+    assert_eq!(error.span.unwrap().line, 2);
+}
+
+#[test]
+fn test_table_merge_error() {
+    let error = parse("--- table!\n_;a\n---").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Table);
+    assert_eq!(error.span.unwrap().line, 2);
+}
+
+#[test]
+fn test_report_render() {
+    colored::control::set_override(false);
+    let source = "first line\n\\(unclosed group";
+    let error = parse(source).unwrap_err();
+    let report = Report::from(error).with_source("doc.arc", source);
+    let rendered = report.render();
+    assert!(rendered.starts_with("error[E0200]: "));
+    assert!(rendered.contains("--> doc.arc:2:"));
+    assert!(rendered.contains("2 | \\(unclosed group"));
+    assert!(rendered.contains("= help:"));
+}
+
+#[test]
+fn test_report_from_string() {
+    colored::control::set_override(false);
+    let report = Report::from("Failed to read file");
+    assert_eq!(report.error.kind, ErrorKind::Io);
+    assert_eq!(report.render(), "error[E0700]: Failed to read file");
+}
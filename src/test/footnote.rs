@@ -0,0 +1,156 @@
+#![cfg(test)]
+
+use super::{body, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::meta::MetaProperties;
+use crate::{Options, compile};
+
+fn warnings(src: &str) -> Vec<String> {
+    compile(src, &Options::default())
+        .unwrap()
+        .diagnostics
+        .warnings()
+        .map(|warning| warning.message.clone())
+        .collect()
+}
+
+#[test]
+fn test_footnote_tokens() {
+    let tokens = Lexer::new(String::from("[^a]: note\ntext[^a] more"))
+        .tokenize()
+        .unwrap();
+    let kinds = tokens
+        .iter()
+        .map(|token| (token.kind.clone(), token.value.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(kinds[0], (TokenKind::FootnoteDefinition, Some("a")));
+    assert_eq!(kinds[1], (TokenKind::String, Some("note")));
+    assert_eq!(kinds[3], (TokenKind::String, Some("text")));
+    assert_eq!(kinds[4], (TokenKind::FootnoteReference, Some("a")));
+    assert_eq!(kinds[5], (TokenKind::String, Some(" more")));
+}
+
+#[test]
+fn test_numbered_by_first_reference() {
+    let html = body("[^b]: Bee.\n[^a]: Ay.\nFirst[^a], second[^b], again[^a].");
+    assert!(html.contains(
+        r##"<span>First</span><sup class="footnote-ref"><a href="#fn-1" id="fnref-1">1</a></sup>"##
+    ));
+    assert!(html.contains(r##"<a href="#fn-2" id="fnref-2">2</a>"##));
+    assert!(html.contains(r##"<a href="#fn-1" id="fnref-1-2">1</a>"##));
+    assert!(html.contains(
+        r##"<section class="footnotes"><ol><li id="fn-1" value="1"><span><span>Ay.</span></span><a class="footnote-back" href="#fnref-1">↩</a></li><li id="fn-2" value="2"><span><span>Bee.</span></span><a class="footnote-back" href="#fnref-2">↩</a></li></ol></section></body>"##
+    ));
+}
+
+#[test]
+fn test_note_holds_inline_content() {
+    let html = body("Text[^1].\n[^1]: See **this** and `code`.");
+    assert!(html.contains(
+        r#"<li id="fn-1" value="1"><span><span>See&nbsp;</span><strong><span>this</span></strong>"#
+    ));
+    assert!(html.contains("<code>code</code>"));
+}
+
+#[test]
+fn test_no_footnotes_no_section() {
+    assert!(!body("plain text").contains("footnotes"));
+}
+
+#[test]
+fn test_numbering_crosses_nested_content() {
+    let src = "A[^x]\n**bold[^y]**\n---table!\ncell[^z]; other\n---\n--- note!\nin a callout[^x][^w]\n[^w]: W.\n---\n[^x]: X.\n[^y]: Y.\n[^z]: Z.";
+    let document = parse(src);
+    let numbers = document
+        .footnotes
+        .numbered()
+        .map(|(number, note)| (number, note.label.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        numbers,
+        vec![
+            (1, String::from("x")),
+            (2, String::from("y")),
+            (3, String::from("z")),
+            (4, String::from("w")),
+        ]
+    );
+    assert!(document.diagnostics.warnings().next().is_none());
+    let html = body(src);
+    assert!(html.contains(r##"id="fnref-1-2">1</a>"##));
+    assert!(html.contains(r##"<a href="#fn-4" id="fnref-4">4</a>"##));
+}
+
+#[test]
+fn test_undefined_and_unreferenced_warnings() {
+    let warnings = warnings("Text[^missing].\n[^unused]: Never cited.");
+    assert_eq!(
+        warnings,
+        vec![
+            String::from("Footnote `[^missing]` is never defined"),
+            String::from("Footnote `[^unused]` is never referenced"),
+        ]
+    );
+    let html = body("Text[^missing].\n[^unused]: Never cited.");
+    assert!(!html.contains("Never"));
+}
+
+#[test]
+fn test_note_numbers_match_references_after_undefined_one() {
+    let html = body("A[^missing] B[^1]\n[^1]: note");
+    assert!(html.contains(r##"<a href="#fn-2" id="fnref-2">2</a>"##));
+    assert!(html.contains(r#"<ol><li id="fn-2" value="2"><span><span>note</span></span>"#));
+}
+
+#[test]
+fn test_duplicate_definition_keeps_first() {
+    let src = "Text[^a].\n[^a]: First.\n[^a]: Second.";
+    assert_eq!(
+        warnings(src),
+        vec![String::from("Footnote `[^a]` is defined twice")]
+    );
+    let html = body(src);
+    assert!(html.contains("First."));
+    assert!(!html.contains("Second."));
+}
+
+#[test]
+fn test_sidenotes() {
+    let html = body("<meta footnotes=sidenotes />\nText[^a] and[^a].\n[^a]: Aside.");
+    assert!(html.contains(
+        r##"<a href="#fn-1" id="fnref-1">1</a></sup><span class="sidenote"><sup>1</sup><span><span>Aside.</span></span></span>"##
+    ));
+    assert_eq!(html.matches(r#"class="sidenote""#).count(), 1);
+    assert!(html.contains(r#"<section class="footnotes with-sidenotes">"#));
+}
+
+#[test]
+fn test_sidenotes_leave_matching_text_alone() {
+    let src = concat!(
+        "<meta footnotes=sidenotes />\n",
+        "--- html!\n",
+        r##"<sup class="footnote-ref"><a href="#fn-1" id="fnref-1">1</a></sup>"##,
+        "\n---\n",
+        "- item with a note[^a]\n",
+        "[^a]: Aside.",
+    );
+    let html = body(src);
+    assert!(html.contains(
+        r##"<body><sup class="footnote-ref"><a href="#fn-1" id="fnref-1">1</a></sup><br /><ul>"##
+    ));
+    assert!(html.contains(
+        r##"<li><span>item&nbsp;with&nbsp;a&nbsp;note</span><sup class="footnote-ref"><a href="#fn-1" id="fnref-1">1</a></sup><span class="sidenote">"##
+    ));
+    assert_eq!(html.matches(r#"class="sidenote""#).count(), 1);
+}
+
+#[test]
+fn test_footnotes_meta_property() {
+    let result = MetaProperties::new(String::from("footnotes=sidenotes"));
+    assert_eq!(result.unwrap(), MetaProperties::Sidenotes(true));
+    let result = MetaProperties::new(String::from("footnotes=endnotes"));
+    assert_eq!(result.unwrap(), MetaProperties::Sidenotes(false));
+    assert!(MetaProperties::new(String::from("footnotes=margin")).is_err());
+}
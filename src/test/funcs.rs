@@ -15,10 +15,10 @@ fn test_invalid_script_content() {
     </script>
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_err());
     assert_eq!(
-        result.unwrap_err(),
+        result.unwrap_err().to_string(),
         format!(
             "Script content is not fully consumed, suggest invalid function syntax. Reminder: {}",
             content
@@ -36,7 +36,7 @@ fn test_document_with_no_script() {
     Something else.
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), content);
 }
@@ -52,7 +52,7 @@ Something else.
 foo()
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
 
     assert_eq!(
         result.unwrap().trim(),
@@ -71,7 +71,7 @@ Something else.
 foo() and foo() again!
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
 
     assert_eq!(
         result.unwrap().trim(),
@@ -88,10 +88,10 @@ fn $foo(*var *var2): bar + *var1 + *var2
 $foo() with no argument should error
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_err());
     assert_eq!(
-        result.unwrap_err(),
+        result.unwrap_err().to_string(),
         "Invalid function arguments: expect 2,but got 0".to_string()
     );
 }
@@ -105,7 +105,7 @@ fn $foo(*var1 *var2): bar + *var1 + *var2
 $foo(%100 %200)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(result.unwrap().trim(), "bar + 100 + 200");
 }
@@ -123,7 +123,7 @@ $bar(%300 %400)
 $const()
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(
         result.unwrap().trim(),
@@ -179,7 +179,7 @@ fn test_inline_function_1() {
 $foo(100) and $foo(%Hello, %world!)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(
         result.unwrap().trim(),
@@ -196,7 +196,7 @@ fn test_inline_function_2() {
 $foo(100) and $foo(%Hello, %world!)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(
         result.unwrap().trim(),
@@ -213,7 +213,7 @@ fn test_inline_function_3() {
 $red(Hello, world!)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert_eq!(result.unwrap().trim(), r#"\(%[red] Hello, world!)"#);
 }
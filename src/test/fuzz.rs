@@ -0,0 +1,203 @@
+#![cfg(test)]
+
+// Property tests: arbitrary input may produce errors, but never a panic.
+
+use crate::lexer::lexer::Lexer;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use crate::utilities::deadline::Deadline;
+use crate::utilities::error::{ArcError, ErrorKind};
+use std::panic;
+use std::time::Duration;
+
+const FRAGMENTS: &[&str] = &[
+    "\n",
+    " ",
+    "text",
+    "é中",
+    "\\(",
+    ")",
+    "\\)",
+    "**",
+    "~",
+    "^",
+    "_",
+    ";",
+    ";;",
+    "[",
+    "]",
+    "%[red]",
+    "%[red:14]",
+    "%[",
+    "%[#fff:300]",
+    "&[",
+    "&[example.com]",
+    "@[",
+    "@[term] 'def'",
+    "'",
+    "<math ",
+    "/>",
+    "<math x^2 />",
+    "<math>",
+    "</math>",
+    "<code>",
+    "<code>:rust\n",
+    "</code>",
+    "--- table!\n",
+    "--- html!\n",
+    "---",
+    "\n---",
+    "(10, 20)",
+    "(",
+    "#",
+    "# ",
+    "#### ",
+    "- ",
+    "1. ",
+    "<meta ",
+    "<meta name=x>",
+    "<meta size=>",
+    "<meta a>",
+    ">",
+    "<script>",
+    "</script>",
+    "fn $f(*a): *a",
+    "$f(",
+    "$f(x)",
+    "|*$g| *",
+    "$g()",
+    "@include <",
+    "///",
+    "\\",
+    "\r",
+    "\t",
+    ":",
+    "=",
+    "%[(",
+    "%[(1,2,3)]",
+    "%[(é]",
+    "--- note!\n",
+    "--- warning! Title\n",
+    "--- tip!",
+    "--- danger!\n",
+    "--- details!\n",
+    "--- details! Summary\n",
+    "--- aside!\n",
+    "> ",
+    ">> ",
+    "> > ",
+    "> -- ",
+    "[^x]",
+    "[^x]: ",
+    "[^",
+    "<meta footnotes=sidenotes />",
+    "<meta pdf-details=collapsed />",
+    "![a] 'b'",
+    "![a] (10, 20) 'b' 'c'",
+    "![",
+    "`",
+    "`code`",
+    "<toc />",
+    "<toc depth=2>",
+    "<meta toc=true />",
+    "<meta toc-depth=2 />",
+    "- [ ] ",
+    "- [x] ",
+    "  - ",
+    "a. ",
+    "iv. ",
+    "IV. ",
+    "(1) ",
+    "5. ",
+    "~~",
+    "__",
+    "^^",
+    ",,",
+    "&>[",
+    "&[#top 'Top']",
+    "&>[mailto:a@b.c]",
+    "###### ",
+    "4294967295. ",
+    "4294967296. ",
+    "(4294967295) ",
+    "18446744073709551616. ",
+    "%[red:4294967296]",
+    "<toc depth=18446744073709551616 />",
+    "<meta toc-depth=4294967295 />",
+    "(1e39, -0)",
+];
+
+/// Xorshift generator, so failures reproduce from the seed alone.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn generate(rng: &mut Rng) -> String {
+    let length = rng.below(40);
+    (0..length)
+        .map(|_| FRAGMENTS[rng.below(FRAGMENTS.len())])
+        .collect()
+}
+
+/// Compiles `src` all the way to HTML. A timeout counts as a failure too, since it
+/// means some loop stopped making progress.
+fn compile(src: String) -> bool {
+    let deadline = Deadline::after(Duration::from_secs(2));
+    let timed_out = |e: &ArcError| e.kind == ErrorKind::Timeout;
+    let tokens = match Lexer::new(src).with_deadline(deadline).tokenize() {
+        Ok(tokens) => tokens,
+        Err(e) => return !timed_out(&e),
+    };
+    match Parser::new(tokens).with_deadline(deadline).parse() {
+        Ok(document) => !document.build().is_empty(),
+        Err(e) => !timed_out(&e),
+    }
+}
+
+fn survives(src: &str) -> bool {
+    let owned = src.to_string();
+    panic::catch_unwind(move || compile(owned)).unwrap_or(false)
+}
+
+// info: the panic hook is global, so it is left alone rather than silenced while
+// other tests run in parallel; a panic prints its message before the test fails
+#[test]
+fn test_random_input_never_panics() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let failure = (0..2000)
+        .map(|_| generate(&mut rng))
+        .find(|src| !survives(src));
+    assert_eq!(failure, None);
+}
+
+#[test]
+fn test_known_crashes() {
+    let inputs = [
+        ")",
+        "text)",
+        "--- table!\n_;a\n---",
+        "--- table!\n=\n---",
+        "--- table!\n(1, 2)\na;^\n---",
+        "\\(<math>x</math>)",
+        "\\(a\\(b) %[red]c)",
+        "\\(a\\(b)~c)",
+        "%[(]x",
+        "%[(é]x",
+        "4294967295. a\ntext\n1. b\n",
+        "(4294967295) a\ntext\n(1) b\n",
+    ];
+    for input in inputs {
+        assert!(survives(input), "input failed: {:?}", input);
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(test)]
+
+use super::body;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+
+#[test]
+fn test_six_heading_levels() {
+    for level in 1..=6 {
+        let html = body(&format!("{} Title", "#".repeat(level)));
+        assert!(
+            html.contains(&format!(
+                r#"<h{0} id="title" class="h{0}size"><span>Title</span></h{0}>"#,
+                level
+            )),
+            "{}",
+            html
+        );
+    }
+}
+
+#[test]
+fn test_seven_hashes_is_text() {
+    let tokens = Lexer::new("####### Title".to_string()).tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::String);
+}
+
+#[test]
+fn test_heading_with_style() {
+    let html = body("## %[red] Results");
+    assert!(
+        html.contains(
+            r#"<h2 id="results" class="h2size" style="color: rgb(255, 0, 0) !important;">"#
+        )
+    );
+}
+
+#[test]
+fn test_no_line_break_after_heading() {
+    let html = body("# Title\nText");
+    assert!(html.contains("</h1><span>"));
+}
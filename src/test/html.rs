@@ -1,12 +1,15 @@
 #![cfg(test)]
 
+use crate::lexer::span::Span;
 use crate::parse::node::ASTNode;
 use crate::parse::node::BlockedContent;
+use crate::parse::node::StyledSyntax;
 
 #[test]
 fn test_html_building_1() {
     let node = ASTNode::BlockedContent {
         content: BlockedContent::PlainText("Hello World".to_string()),
+        span: Span::default(),
     };
     let html = node.build();
     assert_eq!(html.into_string(), "<span>Hello World</span>");
@@ -14,32 +17,48 @@ fn test_html_building_1() {
 
 #[test]
 fn test_html_building_2() {
-    let node = ASTNode::BlockedContent {
-        content: BlockedContent::Bold("Hello World".to_string()),
+    let node = ASTNode::Inline {
+        syntax: vec![StyledSyntax::Bold],
+        content: vec![ASTNode::BlockedContent {
+            content: BlockedContent::PlainText("Hello World".to_string()),
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     let html = node.build();
-    assert_eq!(html.into_string(), "<strong>Hello World</strong>");
+    assert_eq!(
+        html.into_string(),
+        "<strong class=\"\" style=\"\"><span>Hello World</span></strong>"
+    );
 }
 
 #[test]
 fn test_html_building_3() {
-    let node = ASTNode::BlockedContent {
-        content: BlockedContent::Link(
-            "https://www.google.com".to_string(),
-            Some("Google".to_string()),
-        ),
+    let node = ASTNode::Link {
+        href: "https://www.google.com".to_string(),
+        title: None,
+        new_tab: false,
+        content: vec![ASTNode::BlockedContent {
+            content: BlockedContent::PlainText("Google".to_string()),
+            span: Span::default(),
+        }],
+        span: Span::default(),
     };
     let html = node.build();
     assert_eq!(
         html.into_string(),
-        "<a href=\"https://www.google.com\">Google</a>"
+        "<a href=\"https://www.google.com\"><span>Google</span></a>"
     );
 }
 
 #[test]
 fn test_html_building_4() {
-    let node = ASTNode::BlockedContent {
-        content: BlockedContent::Link("https://www.google.com".to_string(), None),
+    let node = ASTNode::Link {
+        href: "https://www.google.com".to_string(),
+        title: None,
+        new_tab: false,
+        content: Vec::new(),
+        span: Span::default(),
     };
     let html = node.build();
     assert_eq!(
@@ -52,6 +71,7 @@ fn test_html_building_4() {
 fn test_html_building_5() {
     let node = ASTNode::BlockedContent {
         content: BlockedContent::Definition("term".to_string(), "definition".to_string()),
+        span: Span::default(),
     };
     let html = node.build();
     assert_eq!(
@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use super::{body, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::node::{ASTNode, BlockedContent};
+
+#[test]
+fn test_image_token() {
+    let tokens = Lexer::new("![img/a.png] (4, 3) 'alt' 'caption'".to_string())
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Image);
+    assert_eq!(
+        tokens[0].value.as_deref(),
+        Some("img/a.png\n(4, 3)\nalt\ncaption")
+    );
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
+}
+
+#[test]
+fn test_image_node() {
+    let document = parse("![../shared/plot.svg] 'A plot'");
+    match &document.nodes[0][0] {
+        ASTNode::Inline { content, .. } => match &content[0] {
+            ASTNode::BlockedContent {
+                content: BlockedContent::Image(src, alt, size, caption),
+                ..
+            } => {
+                assert_eq!(src, "../shared/plot.svg");
+                assert_eq!(alt, "A plot");
+                assert_eq!(size, &(None, None));
+                assert_eq!(caption, &None);
+            }
+            other => panic!("expected an image, got {:?}", other),
+        },
+        other => panic!("expected an inline node, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_image_html() {
+    let html = body("![a.png] 'An image'");
+    assert!(html.contains(r#"<img src="a.png" alt="An image">"#));
+    let html = body("![a.png] (40, 30.5) 'An image'");
+    assert!(
+        html.contains(r#"<img src="a.png" alt="An image" style="width: 400px; height: 305px;">"#)
+    );
+}
+
+#[test]
+fn test_figure_with_caption() {
+    let html = body("![a.png]'Diagram' 'Figure 1'");
+    assert!(html.contains(
+        r#"<figure><img src="a.png" alt="Diagram"><figcaption>Figure&nbsp;1</figcaption></figure>"#
+    ));
+}
+
+#[test]
+fn test_image_in_group() {
+    let html = body("Status: \\(![ok.svg] 'ok') done");
+    assert!(html.contains(r#"<img src="ok.svg" alt="ok">"#));
+    assert!(html.contains("done"));
+}
+
+#[test]
+fn test_image_needs_alt_text() {
+    let html = body("![a.png]");
+    assert!(!html.contains("<img"));
+}
@@ -11,7 +11,7 @@ fn test_std_import_1() {
 $rt(Hello, world!)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     assert!(result.unwrap().trim().contains(r#"\(%[red]Hello, world!)"#));
 }
@@ -27,7 +27,7 @@ $pi()
 $ce(H)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     let unwarped = result.unwrap();
     assert!(unwarped.trim().contains("π"));
@@ -47,7 +47,7 @@ $pi()
 $ce(H)
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     assert!(result.is_ok());
     let unwarped = result.unwrap();
     assert!(unwarped.trim().contains(r#"\(%[red]Hello, world!)"#));
@@ -70,7 +70,7 @@ fn test_custom_import_1() {
 $foo()
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     fs::remove_file("/tmp/import_test.txt").unwrap();
     assert!(result.is_ok());
     assert_eq!(result.unwrap().trim(), "bar");
@@ -84,7 +84,7 @@ fn test_nonexist_std_import() {
 </script>
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     // does not error, only throw runtime warning
     assert!(result.is_ok());
     assert_eq!(result.unwrap().trim(), "");
@@ -98,7 +98,7 @@ fn test_nonexist_custom_import() {
 </script>
 "#;
     let processor = FunctionProcessor::new(content.to_string());
-    let result = processor.process();
+    let result = processor.process().map(|(source, _)| source.text);
     // does not error, only throw runtime warning
     assert!(result.is_ok());
     assert_eq!(result.unwrap().trim(), "");
@@ -1,6 +1,6 @@
 #![cfg(test)]
 use crate::lexer::lexer::Lexer;
-use crate::lexer::token::{Token, TokenKind};
+use crate::lexer::token::TokenKind;
 use crate::lexer::traits::LexerTrait;
 
 #[test]
@@ -12,7 +12,7 @@ fn test_basic_tokenization_1() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::String);
     assert_eq!(tokens[0].value, Some("Hello World".to_string()));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -25,7 +25,7 @@ fn test_basic_tokenization_2() {
     assert_eq!(tokens[0].kind, TokenKind::String);
     assert_eq!(tokens[0].value, Some("Hello World".to_string()));
     assert_eq!(tokens[1].kind, TokenKind::EndOfLine);
-    assert_eq!(tokens[2].kind, TokenKind::EOF);
+    assert_eq!(tokens[2].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -42,7 +42,7 @@ fn test_basic_tokenization_3() {
     assert_eq!(tokens[2].value, Some("Hello World".to_string()));
     assert_eq!(tokens[3].kind, TokenKind::EndOfLine);
     assert_eq!(tokens[4].kind, TokenKind::EndOfLine);
-    assert_eq!(tokens[5].kind, TokenKind::EOF);
+    assert_eq!(tokens[5].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -61,7 +61,7 @@ fn test_basic_tokenization_4() {
     assert_eq!(tokens[4].kind, TokenKind::EndOfLine);
     assert_eq!(tokens[5].kind, TokenKind::String);
     assert_eq!(tokens[5].value, Some("Hello World".to_string()));
-    assert_eq!(tokens[6].kind, TokenKind::EOF);
+    assert_eq!(tokens[6].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -76,7 +76,7 @@ fn test_comment_handling() {
     assert_eq!(tokens[1].kind, TokenKind::EndOfLine);
     assert_eq!(tokens[2].kind, TokenKind::String);
     assert_eq!(tokens[2].value, Some("Hello World".to_string()));
-    assert_eq!(tokens[3].kind, TokenKind::EOF);
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -90,7 +90,7 @@ fn test_comment_stability() {
     assert_eq!(tokens[1].kind, TokenKind::EndOfLine);
     assert_eq!(tokens[2].kind, TokenKind::String);
     assert_eq!(tokens[2].value, Some("some text".to_string()));
-    assert_eq!(tokens[3].kind, TokenKind::EOF);
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -101,7 +101,7 @@ fn test_comment_newline_consume() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::String);
     assert_eq!(tokens[0].value, Some("some text".to_string()));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -120,7 +120,7 @@ fn test_meta_tokenization() {
         tokens[0].value,
         Some("name=My Document key=value ".to_string())
     );
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -143,7 +143,7 @@ fn test_complex_structure_tokenization_1() {
         Some("name=My Document key=value ".to_string())
     );
     assert_eq!(tokens[3].kind, TokenKind::EndOfLine);
-    assert_eq!(tokens[5].kind, TokenKind::EOF);
+    assert_eq!(tokens[5].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -170,7 +170,7 @@ fn test_complex_structure_tokenization_2() {
     assert_eq!(tokens[6].kind, TokenKind::EndOfLine);
     assert_eq!(tokens[7].kind, TokenKind::Definition);
     assert_eq!(tokens[7].value, Some("term-@[]definition".to_string()));
-    assert_eq!(tokens[8].kind, TokenKind::EOF);
+    assert_eq!(tokens[8].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -203,7 +203,7 @@ fn test_nested_parentheses() {
     assert_eq!(tokens[7].kind, TokenKind::RightParenthesis);
     assert_eq!(tokens[8].kind, TokenKind::String);
     assert_eq!(tokens[8].value, Some(" text".to_string()));
-    assert_eq!(tokens[9].kind, TokenKind::EOF);
+    assert_eq!(tokens[9].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -219,7 +219,7 @@ fn test_bold_tokenization() {
     assert_eq!(tokens[1].value, Some("bold text".to_string()));
     assert_eq!(tokens[2].kind, TokenKind::String);
     assert_eq!(tokens[2].value, Some(" here".to_string()));
-    assert_eq!(tokens[3].kind, TokenKind::EOF);
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -232,7 +232,7 @@ fn test_italic_tokenization_1() {
     assert_eq!(tokens[0].kind, TokenKind::Italic);
     assert_eq!(tokens[1].kind, TokenKind::String);
     assert_eq!(tokens[1].value, Some("Some Text".to_string()));
-    assert_eq!(tokens[2].kind, TokenKind::EOF);
+    assert_eq!(tokens[2].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -244,7 +244,7 @@ fn test_tokenization_stability_1() {
     assert_eq!(tokens[0].kind, TokenKind::Italic);
     assert_eq!(tokens[1].kind, TokenKind::String);
     assert_eq!(tokens[1].value, Some("[red:16:(".to_string()));
-    assert_eq!(tokens[2].kind, TokenKind::EOF);
+    assert_eq!(tokens[2].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -259,7 +259,7 @@ fn test_tokenization_stability_2() {
     assert_eq!(tokens[2].kind, TokenKind::RightParenthesis);
     assert_eq!(tokens[3].kind, TokenKind::String);
     assert_eq!(tokens[3].value, Some("] some text next".to_string()));
-    assert_eq!(tokens[4].kind, TokenKind::EOF);
+    assert_eq!(tokens[4].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -278,7 +278,7 @@ fn test_table_tokenization_1() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::Table);
     assert_eq!(tokens[0].value, Some("Hello World\n".to_string()));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -298,7 +298,7 @@ fn test_math_regex_1() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::InlineMath);
     assert_eq!(tokens[0].value, Some("x = 1".to_string()));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -309,7 +309,7 @@ fn test_math_regex_2() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::BlockMath);
     assert_eq!(tokens[0].value, Some("x = 1".to_string()));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -327,7 +327,7 @@ fn test_horizontal_line_1() {
     let tokens = lexer.tokenize().unwrap();
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::HorizontalLine);
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -335,7 +335,11 @@ fn test_invalid_horizontal_line_1() {
     let source = String::from("some text \\(---)");
     let lexer = Lexer::new(source);
     let tokens = lexer.tokenize().unwrap();
-    assert!(!tokens.contains(&Token::new(TokenKind::HorizontalLine, None)));
+    assert!(
+        !tokens
+            .iter()
+            .any(|token| token.kind == TokenKind::HorizontalLine)
+    );
 }
 
 #[test]
@@ -349,7 +353,7 @@ fn test_code_1() {
         tokens[0].value,
         Some(":python\nprint('Hello World')".to_string())
     );
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -363,7 +367,7 @@ fn test_code_2() {
         tokens[0].value,
         Some(String::from("\nprint('Hello World')"))
     );
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -377,7 +381,7 @@ fn test_code_3() {
         tokens[0].value,
         Some(String::from("???\n<, > and /code inside\n"))
     );
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -391,7 +395,7 @@ fn test_code_4() {
         tokens[0].value,
         Some(String::from("python, typescript and rust"))
     );
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
 
 #[test]
@@ -402,5 +406,5 @@ fn test_empty_code() {
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].kind, TokenKind::CodeBlock);
     assert_eq!(tokens[0].value, Some(String::from("\n")));
-    assert_eq!(tokens[1].kind, TokenKind::EOF);
+    assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
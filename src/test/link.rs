@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use super::body;
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::link::resolve_href;
+
+fn link_target(src: &str) -> Option<String> {
+    let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+    match &tokens[0] {
+        token if token.kind == TokenKind::Link => token.value.clone(),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_link_targets() {
+    let targets = [
+        "https://example.com/a?b=c#d",
+        "google.com",
+        "./chapter2.arc",
+        "../notes/intro.arc#summary",
+        "/docs/index.html",
+        "#summary",
+        "mailto:x@y.z",
+        "tel:+1-555-0100",
+        "localhost:8080",
+        "127.0.0.1:3000/api",
+        "appendix.pdf",
+    ];
+    for target in targets {
+        assert_eq!(
+            link_target(&format!("&[{}] text", target)).as_deref(),
+            Some(target),
+            "{}",
+            target
+        );
+    }
+}
+
+#[test]
+fn test_unknown_schemes_are_not_links() {
+    for src in [
+        "&[javascript:alert(1)] x",
+        "&[data:text/html,hi] x",
+        "&[] x",
+    ] {
+        assert_eq!(link_target(src), None, "{}", src);
+    }
+}
+
+#[test]
+fn test_resolve_href() {
+    assert_eq!(resolve_href("./chapter2.arc"), "./chapter2.html");
+    assert_eq!(resolve_href("intro.arc#summary"), "intro.html#summary");
+    assert_eq!(resolve_href("intro.arc?v=2"), "intro.html?v=2");
+    assert_eq!(
+        resolve_href("https://host/file.arc"),
+        "https://host/file.arc"
+    );
+    assert_eq!(resolve_href("localhost:8080/x"), "http://localhost:8080/x");
+    assert_eq!(resolve_href("mailto:x@y.z"), "mailto:x@y.z");
+    assert_eq!(resolve_href("#top"), "#top");
+    assert_eq!(resolve_href("google.com"), "google.com");
+}
+
+#[test]
+fn test_arc_links_render_as_html() {
+    let html = body("&[../week2/notes.arc#proofs] Week 2");
+    assert!(html.contains(r#"<a href="../week2/notes.html#proofs"><span>Week&nbsp;2</span></a>"#));
+}
+
+#[test]
+fn test_rich_link_text() {
+    let html = body("&[#proof] \\(the **proof** of <math x^2 \\geq 0 />) below");
+    assert!(html.contains("<a href=\"#proof\"><span><span>the&nbsp;</span><strong"));
+    assert!(html.contains("</span></a><span>&nbsp;below</span>"));
+}
+
+#[test]
+fn test_link_title_and_new_tab() {
+    assert_eq!(
+        link_target("&>[https://example.com 'Example site'] x").as_deref(),
+        Some("https://example.com\nExample site\n>")
+    );
+    let html = body("&>[https://example.com 'Example site'] Example");
+    assert!(html.contains(
+        r#"<a href="https://example.com" title="Example site" target="_blank" rel="noopener noreferrer"><span>Example</span></a>"#
+    ));
+    let html = body("&>[#top]");
+    assert!(
+        html.contains(r##"<a href="#top" target="_blank" rel="noopener noreferrer">#top</a>"##)
+    );
+}
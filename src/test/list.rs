@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use super::{body, parse};
+use crate::parse::node::{ASTNode, ListMarker, Numbering};
+use crate::parse::tree::TaskCount;
+
+/// Depth of the deepest list in `node`.
+fn depth(node: &ASTNode) -> usize {
+    match node {
+        ASTNode::List { items, .. } => 1 + items.iter().map(depth).max().unwrap_or(0),
+        ASTNode::ListItem { children, .. } => children.iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[test]
+fn test_flat_list() {
+    let document = parse("- a\n- b\n- c");
+    assert_eq!(document.nodes.len(), 1);
+    match &document.nodes[0][0] {
+        ASTNode::List { marker, items, .. } => {
+            assert!(marker.is_none());
+            assert_eq!(items.len(), 3);
+        }
+        other => panic!("expected a list, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_three_levels() {
+    let document = parse("- one\n  - two\n    - three\n  - two again\n- one again");
+    assert_eq!(document.nodes.len(), 1);
+    assert_eq!(depth(&document.nodes[0][0]), 3);
+    assert!(body("- one\n  - two\n    - three").contains(
+        "<ul><li><span>one</span><ul><li><span>two</span><ul><li><span>three</span></li></ul></li></ul></li></ul>"
+    ));
+}
+
+#[test]
+fn test_ordered_inside_unordered() {
+    let html = body("- Week 1\n    1. Intro\n    2. Setup\n- Week 2");
+    assert!(html.contains(
+        "<ul><li><span>Week&nbsp;1</span><ol><li><span>Intro</span></li><li><span>Setup</span></li></ol></li><li><span>Week&nbsp;2</span></li></ul>"
+    ));
+}
+
+#[test]
+fn test_tab_indentation() {
+    let document = parse("1. a\n\t1. b\n\t\t1. c");
+    assert_eq!(depth(&document.nodes[0][0]), 3);
+}
+
+#[test]
+fn test_blank_lines_do_not_split_list() {
+    let document = parse("- a\n\n- b\n\nAfter");
+    assert_eq!(document.nodes.len(), 2);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { items, .. } if items.len() == 2));
+}
+
+#[test]
+fn test_kind_change_starts_new_list() {
+    let html = body("- a\n1. b");
+    assert!(html.contains("<ul><li><span>a</span></li></ul><ol><li><span>b</span></li></ol>"));
+}
+
+fn marker(src: &str) -> Option<ListMarker> {
+    match &parse(src).nodes[0][0] {
+        ASTNode::List { marker, .. } => *marker,
+        other => panic!("expected a list, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_start_number() {
+    assert_eq!(
+        marker("5. five\n6. six"),
+        Some(ListMarker {
+            numbering: Numbering::Decimal,
+            start: 5
+        })
+    );
+    assert!(body("5. five\n6. six").contains("<ol start=\"5\"><li>"));
+    assert!(body("1. one").contains("<ol><li>"));
+}
+
+#[test]
+fn test_custom_markers() {
+    let cases = [
+        ("c. x", Numbering::LowerAlpha, 3),
+        ("b. x", Numbering::LowerAlpha, 2),
+        ("i. x", Numbering::LowerRoman, 1),
+        ("iv. x", Numbering::LowerRoman, 4),
+        ("XII. x", Numbering::UpperRoman, 12),
+        ("(3) x", Numbering::Parenthesized, 3),
+    ];
+    for (src, numbering, start) in cases {
+        assert_eq!(
+            marker(src),
+            Some(ListMarker { numbering, start }),
+            "{}",
+            src
+        );
+    }
+    assert!(body("a. x").contains("<ol style=\"list-style-type: lower-alpha\">"));
+}
+
+#[test]
+fn test_numbering_continues_after_paragraph() {
+    let html = body("1. a\n2. b\nAn aside\n1. c\nAnother\n1. d");
+    assert!(html.contains("<ol><li><span>a</span></li><li><span>b</span></li></ol>"));
+    assert!(html.contains("<ol start=\"3\"><li><span>c</span></li></ol>"));
+    assert!(html.contains("<ol start=\"4\"><li><span>d</span></li></ol>"));
+    let html = body("c. a\nAn aside\na. b");
+    assert!(html.contains(r#"<ol start="4" style="list-style-type: lower-alpha">"#));
+}
+
+#[test]
+fn test_oversized_numbers() {
+    let html = body("4294967295. a\ntext\n1. b");
+    assert!(html.contains(r#"<ol start="4294967295"><li>"#));
+    assert!(html.contains(r#"</span></span><br /><ol><li><span>b"#));
+    assert_eq!(
+        marker("99999999999. a"),
+        Some(ListMarker {
+            numbering: Numbering::Decimal,
+            start: u32::MAX,
+        })
+    );
+}
+
+#[test]
+fn test_numbering_restarts() {
+    for src in [
+        "1. a\nAn aside\n5. b",
+        "1. a\nOne\nTwo\n1. b",
+        "1. a\n# Heading\n1. b",
+        "1. a\nAn aside\na. b",
+    ] {
+        assert!(!body(src).contains("start=\"2\""), "{}", src);
+    }
+}
+
+#[test]
+fn test_marker_needs_space() {
+    let document = parse("e.g. this\nA.B\n12.5 percent");
+    assert!(
+        document
+            .nodes
+            .iter()
+            .all(|line| !matches!(line.as_slice(), [ASTNode::List { .. }]))
+    );
+}
+
+#[test]
+fn test_capital_letter_starts_prose() {
+    let document = parse("A. Lincoln said hi\nB. Obama too\nQ. What now");
+    assert!(
+        document
+            .nodes
+            .iter()
+            .all(|line| !matches!(line.as_slice(), [ASTNode::List { .. }]))
+    );
+    assert!(body("A. Lincoln said hi").contains("<span>A.&nbsp;Lincoln"));
+}
+
+#[test]
+fn test_upper_roman_outline() {
+    let html = body("I. Intro\nII. Next\nIII. Last");
+    assert!(html.contains(concat!(
+        r#"<ol style="list-style-type: upper-roman"><li><span>Intro</span></li>"#,
+        r#"<li><span>Next</span></li><li><span>Last</span></li></ol>"#,
+    )));
+    for (src, start) in [("V. x", 5), ("X. x", 10)] {
+        assert_eq!(
+            marker(src),
+            Some(ListMarker {
+                numbering: Numbering::UpperRoman,
+                start,
+            }),
+            "{}",
+            src
+        );
+    }
+}
+
+#[test]
+fn test_task_items() {
+    let html = body("- [ ] open\n- [x] done\n- plain");
+    assert!(html.contains(r#"<li><input type="checkbox" disabled><span>open</span></li>"#));
+    assert!(html.contains(r#"<li><input type="checkbox" disabled checked><span>done</span></li>"#));
+    assert!(html.contains("<li><span>plain</span></li>"));
+}
+
+#[test]
+fn test_task_count() {
+    let document =
+        parse("- [ ] a\n  - [X] b\n  - [ ] c\n- [x] d\n1. [x] not a task\n- [y] not a task");
+    assert_eq!(document.tasks(), TaskCount { open: 2, done: 2 });
+}
@@ -6,30 +6,30 @@ use crate::parse::meta::MetaProperties;
 fn test_from_string_1() {
     let input = "name=My Document";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::Name(ref s)) if s == "My Document"));
+    assert!(matches!(result, Ok(MetaProperties::Name(ref s)) if s == "My Document"));
 
     let input = "font-size=16";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontSize(16))));
+    assert!(matches!(result, Ok(MetaProperties::FontSize(16))));
 
     let input = "font-color=(255, 0, 0)";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontColor(_))));
+    assert!(matches!(result, Ok(MetaProperties::FontColor(_))));
 }
 
 #[test]
 fn test_meta_properties_new_2() {
     let input = "name =My Document";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::Name(ref s)) if s == "My Document"));
+    assert!(matches!(result, Ok(MetaProperties::Name(ref s)) if s == "My Document"));
 
     let input = "font-size= 16 ";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontSize(16))));
+    assert!(matches!(result, Ok(MetaProperties::FontSize(16))));
 
     let input = "  font-color= (255, 0, 0)";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontColor(_))));
+    assert!(matches!(result, Ok(MetaProperties::FontColor(_))));
 }
 
 #[test]
@@ -37,7 +37,7 @@ fn test_color_building_process() {
     let input = "font-color=(255, 0, 0)";
     let result = MetaProperties::new(String::from(input));
 
-    if let Some(MetaProperties::FontColor(color)) = result {
+    if let Ok(MetaProperties::FontColor(color)) = result {
         assert_eq!(color.build(), "rgb(255, 0, 0)");
     } else {
         panic!("Expected font-color");
@@ -54,7 +54,7 @@ fn test_invalid_meta_property() {
 fn test_invalid_color() {
     let input = "font-color=invalid";
     let result = MetaProperties::new(String::from(input));
-    assert!(result.is_none());
+    assert!(result.is_err());
 }
 
 #[test]
@@ -67,22 +67,22 @@ fn test_case_sensitivity() {
 fn test_whitespace_handling() {
     let input = " name = My Document with Spaces ";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::Name(ref s)) if s == "My Document with Spaces"));
+    assert!(matches!(result, Ok(MetaProperties::Name(ref s)) if s == "My Document with Spaces"));
 
     let input = "font-size =  20  ";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontSize(20))));
+    assert!(matches!(result, Ok(MetaProperties::FontSize(20))));
 
     let input = "font-color  =  (100,  150,  200) ";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::FontColor(_))));
+    assert!(matches!(result, Ok(MetaProperties::FontColor(_))));
 }
 
 #[test]
 fn test_empty_values() {
     let input = "name=";
     let result = MetaProperties::new(String::from(input));
-    assert!(result.is_none());
+    assert!(result.is_err());
 }
 
 #[test]
@@ -95,7 +95,7 @@ fn test_missing_equals_sign() {
 fn test_multiple_equals_signs() {
     let input = "name=My=Document";
     let result = MetaProperties::new(String::from(input));
-    assert!(matches!(result, Some(MetaProperties::Name(ref s)) if s == "My=Document"));
+    assert!(matches!(result, Ok(MetaProperties::Name(ref s)) if s == "My=Document"));
 }
 
 #[test]
@@ -109,3 +109,25 @@ fn test_invalid_boolean_value() {
     let input = "allow-html=invalid";
     let _ = MetaProperties::new(String::from(input));
 }
+
+#[test]
+fn test_h5_h6_properties() {
+    let result = MetaProperties::new(String::from("h5-font-size=14"));
+    assert!(matches!(result, Ok(MetaProperties::H5TagFontSize(14))));
+    let result = MetaProperties::new(String::from("h6-font-color=gray"));
+    assert!(matches!(result, Ok(MetaProperties::H6TagFontColor(_))));
+    assert_eq!(
+        MetaProperties::H6TagFontSize(12).build(),
+        "<style>.h6size { font-size: 12px !important; }</style>"
+    );
+}
+
+#[test]
+fn test_pdf_details_property() {
+    let result = MetaProperties::new(String::from("pdf-details=collapsed"));
+    assert_eq!(result.unwrap(), MetaProperties::PdfDetails(false));
+    let result = MetaProperties::new(String::from("pdf-details=expanded"));
+    assert_eq!(result.unwrap(), MetaProperties::PdfDetails(true));
+    assert!(MetaProperties::new(String::from("pdf-details=open")).is_err());
+    assert_eq!(MetaProperties::PdfDetails(true).build(), "");
+}
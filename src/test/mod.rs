@@ -1,14 +1,55 @@
 #![cfg(test)]
+use crate::lexer::lexer::Lexer;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+use crate::parse::tree::Document;
+
+mod api;
+mod bench;
+mod callout;
+mod code;
 mod color;
+mod deadline;
+mod details;
+mod diagnostics;
+mod emphasis;
+mod error;
+mod footnote;
 mod funcs;
+mod fuzz;
+mod heading;
 mod html;
+mod image;
 mod import;
 mod lexer;
+mod link;
+mod list;
 mod meta;
 mod node;
 mod parse;
 mod parse_html;
+mod quote;
+mod recovery;
 mod regex1;
 mod regex2;
+mod span;
 mod table;
+mod toc;
 mod tree;
+
+/// Lexes and parses `src`, failing the test on any error.
+fn parse(src: &str) -> Document {
+    let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
+
+/// The rendered page of `src` from its `<body>` tag on.
+fn body(src: &str) -> String {
+    body_of(&parse(src))
+}
+
+fn body_of(document: &Document) -> String {
+    let html = document.build();
+    let start = html.find("<body>").unwrap();
+    html[start..].to_string()
+}
@@ -58,14 +58,20 @@ fn test_styled_syntax_new_style_5() {
 fn test_styled_syntax_new_style_6() {
     let syntax = StyledSyntax::new_style("".to_string());
     assert!(syntax.is_err());
-    assert_eq!(syntax.unwrap_err(), "Invalid style syntax: Empty");
+    assert_eq!(
+        syntax.unwrap_err().to_string(),
+        "Invalid style syntax: Empty"
+    );
 }
 
 #[test]
 fn test_styled_syntax_new_style_7() {
     let syntax = StyledSyntax::new_style("::".to_string());
     assert!(syntax.is_err());
-    assert_eq!(syntax.unwrap_err(), "Invalid style syntax: Empty");
+    assert_eq!(
+        syntax.unwrap_err().to_string(),
+        "Invalid style syntax: Empty"
+    );
 }
 
 #[test]
@@ -84,7 +90,7 @@ fn test_styled_syntax_rgb_1() {
     assert!(syntax.is_ok());
     let syntax = syntax.unwrap();
     assert!(
-        matches!(syntax, StyledSyntax::Style((None, None, Some(color))) if matches!(color, Color::RGB(255, 0, 0)))
+        matches!(syntax, StyledSyntax::Style((None, None, Some(color))) if matches!(color, Color::Rgb(255, 0, 0)))
     );
 }
 
@@ -94,7 +100,7 @@ fn test_styled_syntax_rgb_2() {
     assert!(syntax.is_ok());
     let syntax = syntax.unwrap();
     assert!(
-        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::Literal(ColorLiteral::Red)) && matches!(color2, Color::RGB(255, 0, 0)))
+        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::Literal(ColorLiteral::Red)) && matches!(color2, Color::Rgb(255, 0, 0)))
     );
 }
 
@@ -104,7 +110,7 @@ fn test_styled_syntax_rgb_3() {
     assert!(syntax.is_ok());
     let syntax = syntax.unwrap();
     assert!(
-        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::RGB(255, 0, 0)) && matches!(color2, Color::Literal(ColorLiteral::Blue)))
+        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::Rgb(255, 0, 0)) && matches!(color2, Color::Literal(ColorLiteral::Blue)))
     );
 }
 
@@ -114,7 +120,7 @@ fn test_styled_syntax_rgb_4() {
     assert!(syntax.is_ok());
     let syntax = syntax.unwrap();
     assert!(
-        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::RGB(255, 0, 0)) && matches!(color2, Color::RGB(0, 0, 255)))
+        matches!(syntax, StyledSyntax::Style((Some(color1), Some(16), Some(color2))) if matches!(color1, Color::Rgb(255, 0, 0)) && matches!(color2, Color::Rgb(0, 0, 255)))
     );
 }
 
@@ -124,7 +130,7 @@ fn test_styled_syntax_rgb_5() {
     assert!(syntax.is_ok());
     let syntax = syntax.unwrap();
     assert!(
-        matches!(syntax, StyledSyntax::Style((Some(color), None, None)) if matches!(color, Color::RGB(255, 0, 0)))
+        matches!(syntax, StyledSyntax::Style((Some(color), None, None)) if matches!(color, Color::Rgb(255, 0, 0)))
     );
 }
 
@@ -133,7 +139,7 @@ fn test_invalid_style_1() {
     let syntax = StyledSyntax::new_style("red:16:blue:extra".to_string());
     assert!(syntax.is_err());
     assert_eq!(
-        syntax.unwrap_err(),
+        syntax.unwrap_err().to_string(),
         "Invalid style syntax: red:16:blue:extra"
     );
 }
@@ -142,7 +148,10 @@ fn test_invalid_style_1() {
 fn test_invalid_style_2() {
     let syntax = StyledSyntax::new_style("invalid".to_string());
     assert!(syntax.is_err());
-    assert_eq!(syntax.unwrap_err(), "Invalid color literal: invalid");
+    assert_eq!(
+        syntax.unwrap_err().to_string(),
+        "Invalid color literal: invalid"
+    );
 }
 
 #[test]
@@ -150,7 +159,7 @@ fn test_invalid_style_3() {
     let syntax = StyledSyntax::new_style("red:invalid".to_string());
     assert!(syntax.is_err());
     assert_eq!(
-        syntax.unwrap_err(),
+        syntax.unwrap_err().to_string(),
         "Invalid value for font size: 'invalid', msg:`invalid digit found in string`"
     );
 }
@@ -159,7 +168,10 @@ fn test_invalid_style_3() {
 fn test_invalid_style_4() {
     let syntax = StyledSyntax::new_style("red:16:invalid".to_string());
     assert!(syntax.is_err());
-    assert_eq!(syntax.unwrap_err(), "Invalid color literal: invalid");
+    assert_eq!(
+        syntax.unwrap_err().to_string(),
+        "Invalid color literal: invalid"
+    );
 }
 
 #[test]
@@ -167,7 +179,7 @@ fn test_invalid_style_5() {
     let syntax = StyledSyntax::new_style("red:16:(255, 0, 0, 0)".to_string());
     assert!(syntax.is_err());
     assert_eq!(
-        syntax.unwrap_err(),
+        syntax.unwrap_err().to_string(),
         "Too many values for rgb literal: (255, 0, 0, 0)"
     );
 }
@@ -177,7 +189,7 @@ fn test_invalid_style_6() {
     let syntax = StyledSyntax::new_style("red:16:(255, 0)".to_string());
     assert!(syntax.is_err());
     assert_eq!(
-        syntax.unwrap_err(),
+        syntax.unwrap_err().to_string(),
         "Insufficient values for rgb literal: (255, 0)"
     );
 }
@@ -11,7 +11,7 @@ fn test_parser_debug() {
     let parser = Parser::new(Vec::new());
     assert_eq!(
         format!("{:?}", parser),
-        "Parser { source: [], document: Document { meta: [], nodes: [] } }"
+        "Parser { source: [], document: Document { meta: [], nodes: [], footnotes: Footnotes { references: [], notes: [] }, diagnostics: Diagnostics { entries: [] } }, previous: Span(0:0, 0..0), deadline: Deadline(none), safe_mode: false }"
     );
 }
 
@@ -28,7 +28,7 @@ fn test_basic_parsing_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(1:1, 0..11) }], span: Span(1:1, 0..11) }"
     );
 }
 
@@ -46,11 +46,11 @@ fn test_basic_parsing_2() {
     assert_eq!(document.nodes[1].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(1:1, 0..11) }], span: Span(1:1, 0..11) }"
     );
     assert_eq!(
         format!("{:?}", document.nodes[1][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(2:1, 12..23) }], span: Span(2:1, 12..23) }"
     );
 }
 
@@ -70,16 +70,16 @@ fn test_basic_parsing_3() {
     assert_eq!(document.nodes[3].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(1:1, 0..11) }], span: Span(1:1, 0..11) }"
     );
     assert_eq!(
         format!("{:?}", document.nodes[1][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(2:1, 12..23) }], span: Span(2:1, 12..23) }"
     );
     assert_eq!(format!("{:?}", document.nodes[2]), "[]");
     assert_eq!(
         format!("{:?}", document.nodes[3][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(4:1, 25..36) }], span: Span(4:1, 25..36) }"
     );
 }
 
@@ -100,7 +100,7 @@ fn test_meta_parsing_1() {
     assert_eq!(document.nodes[1].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[1][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(2:1, 36..47) }], span: Span(2:1, 36..47) }"
     );
 }
 
@@ -123,12 +123,12 @@ fn test_meta_parsing_2() {
     assert_eq!(document.nodes[2].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[2][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(3:1, 44..55) }], span: Span(3:1, 44..55) }"
     );
     assert_eq!(document.nodes[3].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[3][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"This is test\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"This is test\"), span: Span(4:1, 56..68) }], span: Span(4:1, 56..68) }"
     );
 }
 
@@ -146,7 +146,7 @@ fn test_complex_expression_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [Style((Some(Literal(Red)), Some(16), Some(RGB(255, 0, 0))))], content: [BlockedContent { content: PlainText(\"some text next\") }] }"
+        "Inline { syntax: [Style((Some(Literal(Red)), Some(16), Some(Rgb(255, 0, 0))))], content: [BlockedContent { content: PlainText(\"some text next\"), span: Span(1:23, 22..36) }], span: Span(1:1, 0..36) }"
     );
 }
 
@@ -163,7 +163,7 @@ fn test_complex_expression_2() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [Italic, Style((Some(Literal(Red)), Some(16), Some(RGB(255, 0, 0))))], content: [BlockedContent { content: PlainText(\"some text next\") }] }"
+        "Inline { syntax: [Italic, Style((Some(Literal(Red)), Some(16), Some(Rgb(255, 0, 0))))], content: [BlockedContent { content: PlainText(\"some text next\"), span: Span(1:25, 24..38) }], span: Span(1:1, 0..38) }"
     );
 }
 
@@ -181,7 +181,7 @@ fn test_complex_expression_3() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [Italic, Style((Some(Literal(Red)), None, None))], content: [BlockedContent { content: PlainText(\"some char \") }, Inline { syntax: [], content: [BlockedContent { content: Link(\"www.google.com/path/to/page\", Some(\"some char\")) }] }] }"
+        "Inline { syntax: [Italic, Style((Some(Literal(Red)), None, None))], content: [BlockedContent { content: PlainText(\"some char \"), span: Span(1:10, 9..19) }, Inline { syntax: [], content: [Link { href: \"www.google.com/path/to/page\", title: None, new_tab: false, content: [BlockedContent { content: PlainText(\"some char\"), span: Span(1:53, 52..61) }], span: Span(1:22, 21..61) }], span: Span(1:22, 21..61) }], span: Span(1:1, 0..62) }"
     );
 }
 
@@ -213,7 +213,7 @@ fn test_definition_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: Definition(\"term\", \"definition\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: Definition(\"term\", \"definition\"), span: Span(1:1, 0..20) }], span: Span(1:1, 0..20) }"
     );
 }
 
@@ -230,7 +230,7 @@ fn test_definition_2() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: Definition(\"term, term2, term3\", \"definition, definition2, definition3\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: Definition(\"term, term2, term3\", \"definition, definition2, definition3\"), span: Span(1:1, 0..60) }], span: Span(1:1, 0..60) }"
     );
 }
 
@@ -248,7 +248,7 @@ fn test_heading_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [Heading(1)], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [Heading(1), Anchor(\"hello-world\")], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(1:3, 2..13) }], span: Span(1:1, 0..13) }"
     );
 }
 
@@ -266,11 +266,11 @@ fn test_multiple_headings() {
     assert_eq!(document.nodes[1].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [Heading(1)], content: [BlockedContent { content: PlainText(\"Hello World\") }] }"
+        "Inline { syntax: [Heading(1), Anchor(\"hello-world\")], content: [BlockedContent { content: PlainText(\"Hello World\"), span: Span(1:3, 2..13) }], span: Span(1:1, 0..13) }"
     );
     assert_eq!(
         format!("{:?}", document.nodes[1][0]),
-        "Inline { syntax: [Heading(2)], content: [BlockedContent { content: PlainText(\"This is next line\") }] }"
+        "Inline { syntax: [Heading(2), Anchor(\"this-is-next-line\")], content: [BlockedContent { content: PlainText(\"This is next line\"), span: Span(2:4, 17..34) }], span: Span(2:1, 14..34) }"
     );
 }
 
@@ -286,21 +286,21 @@ fn test_deep_nesting() {
     assert_eq!(document.nodes[0].len(), 1);
 
     // Check the structure matches what we see in the output
-    if let ASTNode::Inline { syntax: _, content } = &document.nodes[0][0] {
+    if let ASTNode::Inline { content, .. } = &document.nodes[0][0] {
         assert_eq!(content.len(), 3); // "Hello, ", nested content, and "world."
 
         // Check the nested content (second element)
         if let ASTNode::Inline {
-            syntax: _,
             content: inner_content,
+            ..
         } = &content[1]
         {
             assert_eq!(inner_content.len(), 3); // "inner 1 ", nested content, and "inner 1"
 
             // Check the deeper nested content
             if let ASTNode::Inline {
-                syntax: _,
                 content: deeper_content,
+                ..
             } = &inner_content[1]
             {
                 assert_eq!(deeper_content.len(), 3); // "inner 2 ", nested content, and "inner 2"
@@ -319,7 +319,8 @@ fn test_ordered_list_1() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    assert_eq!(document.nodes.len(), 5); // 3 list, 2 list indicators
+    assert_eq!(document.nodes.len(), 1);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { items, .. } if items.len() == 3));
 }
 
 #[test]
@@ -332,7 +333,8 @@ fn test_ordered_list_2() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    assert_eq!(document.nodes.len(), 6);
+    assert_eq!(document.nodes.len(), 1);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { items, .. } if items.len() == 4));
 }
 
 #[test]
@@ -344,7 +346,8 @@ fn test_ordered_list_with_styling() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    assert_eq!(document.nodes.len(), 5); // 3 list, 2 list indicators
+    assert_eq!(document.nodes.len(), 1);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { items, .. } if items.len() == 3));
 }
 
 #[test]
@@ -355,7 +358,8 @@ fn test_unordered_list_1() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    assert_eq!(document.nodes.len(), 5); // 3 list, 2 list indicators
+    assert_eq!(document.nodes.len(), 1);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { items, .. } if items.len() == 3));
 }
 
 #[test]
@@ -368,7 +372,7 @@ fn test_nesting_right_parenthesis() {
 
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        r#"Inline { syntax: [], content: [Inline { syntax: [Style((Some(Literal(Yellow)), None, None))], content: [BlockedContent { content: PlainText("(second time parsing") }, BlockedContent { content: PlainText(")") }] }] }"#
+        r#"Inline { syntax: [], content: [Inline { syntax: [Style((Some(Literal(Yellow)), None, None))], content: [BlockedContent { content: PlainText("(second time parsing"), span: Span(1:12, 11..31) }, BlockedContent { content: PlainText(")"), span: Span(1:32, 31..33) }], span: Span(1:3, 2..33) }], span: Span(1:1, 0..34) }"#
     );
 }
 
@@ -384,7 +388,7 @@ fn test_triple_tide() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"~~~Hello World~~~\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"~~~Hello World~~~\"), span: Span(1:1, 0..17) }], span: Span(1:1, 0..17) }"
     );
 }
 
@@ -466,7 +470,12 @@ fn test_ordered_with_unordered_list() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    assert_eq!(document.nodes.len(), 10);
+    assert_eq!(document.nodes.len(), 2);
+    assert!(matches!(&document.nodes[0][0], ASTNode::List { marker, .. } if marker.is_some()));
+    assert!(matches!(
+        &document.nodes[1][0],
+        ASTNode::List { marker: None, .. }
+    ));
 }
 
 #[test]
@@ -481,7 +490,7 @@ fn test_horizontal_line_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "Indicator { indicate: HorizontalLine }"
+        "Indicator { indicate: HorizontalLine, span: Span(1:1, 0..3) }"
     );
 }
 
@@ -497,7 +506,7 @@ fn test_horizontal_line_2() {
     assert_eq!(document.nodes[1].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[1][0]),
-        "Indicator { indicate: HorizontalLine }"
+        "Indicator { indicate: HorizontalLine, span: Span(2:1, 12..15) }"
     );
 }
 
@@ -509,13 +518,14 @@ fn test_invalid_horizontal_line_1() {
     let parser = Parser::new(tokens);
     let document = parser.parse().unwrap();
 
-    let contains_horizontal = document.nodes.iter().any(|node| match node.as_slice() {
-        [
-            ASTNode::Indicator {
+    let contains_horizontal = document.nodes.iter().any(|node| {
+        matches!(
+            node.as_slice(),
+            [ASTNode::Indicator {
                 indicate: Indicator::HorizontalLine,
-            },
-        ] => true,
-        _ => false,
+                ..
+            }]
+        )
     });
     assert!(!contains_horizontal);
 }
@@ -532,7 +542,7 @@ fn test_code_1() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "BlockedContent { content: CodeBlock(\"python\", \"print('Hello World')\") }"
+        "BlockedContent { content: CodeBlock(\"python\", \"print('Hello World')\"), span: Span(1:1, 0..41) }"
     );
 }
 #[test]
@@ -547,7 +557,7 @@ fn test_code_2() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        "BlockedContent { content: CodeBlock(\"\", \"print('Hello World')\") }"
+        "BlockedContent { content: CodeBlock(\"\", \"print('Hello World')\"), span: Span(1:1, 0..34) }"
     );
 }
 #[test]
@@ -562,7 +572,7 @@ fn test_code_3() {
     assert_eq!(document.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", document.nodes[0][0]),
-        r#"BlockedContent { content: CodeBlock("", "<, > and /code inside") }"#
+        r#"BlockedContent { content: CodeBlock("", "<, > and /code inside"), span: Span(1:1, 0..39) }"#
     );
 }
 #[test]
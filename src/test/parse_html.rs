@@ -25,7 +25,7 @@ fn test_html_parsing_1() {
     assert_eq!(result.nodes[0].len(), 1);
     assert_eq!(
         format!("{:?}", result.nodes[0][0]),
-        "BlockedContent { content: HTMLContainer(\"<div>\\n<p>Hello World</p>\\n</div>\") }"
+        "BlockedContent { content: HTMLContainer(\"<div>\\n<p>Hello World</p>\\n</div>\"), span: Span(1:1, 0..44) }"
     );
 }
 
@@ -51,11 +51,11 @@ fn test_html_parsing_2() {
     assert_eq!(result.nodes[1].len(), 1);
     assert_eq!(
         format!("{:?}", result.nodes[0][0]),
-        "BlockedContent { content: HTMLContainer(\"<div>\\n<p>Hello World</p>\\n<p>This is next line</p>\\n<p>This is next line</p>\\n</div>\") }"
+        "BlockedContent { content: HTMLContainer(\"<div>\\n<p>Hello World</p>\\n<p>This is next line</p>\\n<p>This is next line</p>\\n</div>\"), span: Span(1:1, 0..95) }"
     );
     assert_eq!(
         format!("{:?}", result.nodes[1][0]),
-        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"This is next line\") }] }"
+        "Inline { syntax: [], content: [BlockedContent { content: PlainText(\"This is next line\"), span: Span(9:1, 96..113) }], span: Span(9:1, 96..113) }"
     );
 }
 
@@ -0,0 +1,121 @@
+#![cfg(test)]
+
+use super::{body, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::node::ASTNode;
+
+/// Depth of the deepest quote in `node`.
+fn depth(node: &ASTNode) -> usize {
+    match node {
+        ASTNode::BlockQuote { content, .. } => 1 + content.iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[test]
+fn test_quote_marker_tokens() {
+    for (src, marker) in [
+        ("> a", ">"),
+        (">> a", ">>"),
+        ("> > a", "> >"),
+        ("> -- a", "> --"),
+        (">\n", ">"),
+    ] {
+        let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+        assert_eq!(tokens[0].kind, TokenKind::BlockQuote, "{}", src);
+        assert_eq!(tokens[0].value.as_deref(), Some(marker), "{}", src);
+    }
+}
+
+#[test]
+fn test_marker_needs_space() {
+    let tokens = Lexer::new(String::from(">a")).tokenize().unwrap();
+    assert_ne!(tokens[0].kind, TokenKind::BlockQuote);
+}
+
+#[test]
+fn test_consecutive_lines_form_one_quote() {
+    let document = parse("> a\n> b\n> c");
+    assert_eq!(document.nodes.len(), 1);
+    match &document.nodes[0][0] {
+        ASTNode::BlockQuote {
+            content,
+            attribution,
+            ..
+        } => {
+            assert_eq!(content.len(), 3);
+            assert!(attribution.is_none());
+        }
+        other => panic!("expected a quote, got {:?}", other),
+    }
+    assert!(body("> a\n> b").contains(
+        "<blockquote><span><span>a</span></span><br /><span><span>b</span></span></blockquote>"
+    ));
+}
+
+#[test]
+fn test_blank_line_ends_quote() {
+    let document = parse("> a\n\n> b");
+    let quotes = document
+        .nodes
+        .iter()
+        .flatten()
+        .filter(|node| matches!(node, ASTNode::BlockQuote { .. }))
+        .count();
+    assert_eq!(quotes, 2);
+}
+
+#[test]
+fn test_nested_quotes() {
+    let document = parse("> one\n>> two\n> > > three\n> one again");
+    assert_eq!(document.nodes.len(), 1);
+    assert_eq!(depth(&document.nodes[0][0]), 3);
+    assert!(body("> one\n>> two\n> back").contains(
+        "<blockquote><span><span>one</span></span><blockquote><span><span>two</span></span></blockquote><span><span>back</span></span></blockquote>"
+    ));
+}
+
+#[test]
+fn test_attribution() {
+    let html = body("> Stay hungry.\n> -- Steve Jobs\nafter");
+    assert!(html.contains(
+        "<span><span>Stay&nbsp;hungry.</span></span><cite><span><span>Steve&nbsp;Jobs</span></span></cite></blockquote><span><span>after</span></span>"
+    ));
+}
+
+#[test]
+fn test_nested_attribution_stays_with_its_quote() {
+    let document = parse("> outer\n>> inner\n>> -- Inner Author\n> -- Outer Author");
+    let ASTNode::BlockQuote {
+        content,
+        attribution,
+        ..
+    } = &document.nodes[0][0]
+    else {
+        panic!("expected a quote");
+    };
+    assert!(attribution.is_some());
+    assert!(matches!(
+        &content[1],
+        ASTNode::BlockQuote {
+            attribution: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_inline_content() {
+    let html = body("> %[red] **bold** and `code`");
+    assert!(html.contains("<strong><span>bold</span></strong>"));
+    assert!(html.contains("<code>code</code>"));
+    assert!(html.contains("color: rgb(255, 0, 0) !important;"));
+}
+
+#[test]
+fn test_quote_after_list() {
+    let html = body("- item\n> quote");
+    assert!(html.contains("</ul><blockquote>"));
+}
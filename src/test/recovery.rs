@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::node::ASTNode;
+use crate::parse::parse::Parser;
+use crate::parse::tree::Document;
+use crate::utilities::error::ErrorKind;
+
+fn parse_all(source: &str) -> Document {
+    let (tokens, diagnostics) = Lexer::new(source.to_string())
+        .tokenize_with_diagnostics()
+        .unwrap();
+    Parser::new(tokens)
+        .with_diagnostics(diagnostics)
+        .parse_with_recovery()
+        .unwrap()
+}
+
+#[test]
+fn test_recover_multiple_errors() {
+    let document = parse_all("one\n\\(two\nthree\n\\(four\nfive");
+    let lines: Vec<usize> = document
+        .diagnostics
+        .errors()
+        .map(|e| e.span.unwrap().line)
+        .collect();
+    assert_eq!(lines, vec![2, 4]);
+}
+
+#[test]
+fn test_recover_keeps_following_lines() {
+    let document = parse_all("\\(broken\nstill here");
+    let html = document.build();
+    assert!(html.contains("still&nbsp;here"));
+}
+
+#[test]
+fn test_recover_error_placeholder() {
+    let document = parse_all("\\(broken");
+    let placeholder = document
+        .nodes
+        .iter()
+        .flatten()
+        .find(|node| matches!(node, ASTNode::Error { .. }));
+    assert!(placeholder.is_some());
+    assert_eq!(placeholder.unwrap().span().line, 1);
+}
+
+#[test]
+fn test_parse_stops_at_first_error() {
+    let tokens = Lexer::new("ok\n\\(first\n\\(second".to_string())
+        .tokenize()
+        .unwrap();
+    let error = Parser::new(tokens).parse().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Parser);
+    assert_eq!(error.span.unwrap().line, 2);
+}
+
+#[test]
+fn test_lexer_recovers_unmatched_text() {
+    let (tokens, diagnostics) = Lexer::new("fine\nunclosed ** bold\nfine".to_string())
+        .tokenize_with_diagnostics()
+        .unwrap();
+    assert!(tokens.iter().any(|t| t.kind == TokenKind::Error));
+    let error = diagnostics.errors().next().unwrap();
+    assert_eq!(error.kind, ErrorKind::Lexer);
+    assert_eq!(error.span.unwrap().line, 2);
+}
@@ -477,6 +477,22 @@ fn test_string_regex_9() {
     assert_eq!(matched.as_str(), "Hello ");
 }
 
+#[test]
+fn test_string_regex_10() {
+    let regex = Regex::new(STRING_REGEX).unwrap();
+    let matched = regex.find("a &[https://x.com] b").unwrap().unwrap();
+    assert_eq!(matched.as_str(), "a ");
+    let matched = regex.find("a %[red] b").unwrap().unwrap();
+    assert_eq!(matched.as_str(), "a ");
+}
+
+#[test]
+fn test_string_regex_11() {
+    let regex = Regex::new(STRING_REGEX).unwrap();
+    let matched = regex.find("a &[ftp://x] &[no end %[ b").unwrap().unwrap();
+    assert_eq!(matched.as_str(), "a &[ftp://x] &[no end %[ b");
+}
+
 #[test]
 fn test_definition_regex_1() {
     let regex = Regex::new(DEFINITION_REGEX).unwrap();
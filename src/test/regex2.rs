@@ -71,20 +71,6 @@ fn test_table_container_capture_2() {
     assert_eq!(capture.as_str(), "Hello, world!\n\nHello World");
 }
 
-#[test]
-fn test_multiple_newline_regex_1() {
-    let regex = Regex::new(MULTIPLE_NEWLINE_REGEX).unwrap();
-    let matched = regex.find("\n\n\n\n").unwrap().unwrap();
-    assert_eq!(matched.as_str(), "\n\n\n\n");
-}
-
-#[test]
-#[should_panic]
-fn test_multiple_newline_regex_2() {
-    let regex = Regex::new(MULTIPLE_NEWLINE_REGEX).unwrap();
-    let _ = regex.find("\n").unwrap().unwrap();
-}
-
 #[test]
 #[should_panic]
 fn test_math_1() {
@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use crate::lexer::lexer::Lexer;
+use crate::lexer::span::Span;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::parse::Parser;
+
+#[test]
+fn test_token_span_1() {
+    let source = "Hello\nWorld".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    assert_eq!(tokens[0].span, Span::new(0, 5, 1, 1));
+    assert_eq!(tokens[1].span, Span::new(5, 6, 1, 6));
+    assert_eq!(tokens[2].span, Span::new(6, 11, 2, 1));
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
+    assert_eq!(tokens[3].span, Span::new(11, 11, 2, 6));
+}
+
+#[test]
+fn test_token_span_unicode_column() {
+    let source = "π\nüü \\(x)".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    let group = tokens
+        .iter()
+        .find(|token| token.kind == TokenKind::BackSlashLeftParenthesisInline)
+        .unwrap();
+    assert_eq!(group.span, Span::new(8, 10, 2, 4));
+}
+
+#[test]
+fn test_token_span_after_line_continuation() {
+    let source = "first \\\nsecond\nthird".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    assert_eq!(tokens[0].value, Some("first second".to_string()));
+    assert_eq!(tokens[0].span, Span::new(0, 14, 1, 1));
+    assert_eq!(tokens[2].value, Some("third".to_string()));
+    assert_eq!(tokens[2].span, Span::new(15, 20, 3, 1));
+}
+
+#[test]
+fn test_token_span_after_comment() {
+    let source = "text /// comment\r\n/// whole line\nnext".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    let next = tokens
+        .iter()
+        .find(|token| token.value.as_deref() == Some("next"))
+        .unwrap();
+    assert_eq!(next.span, Span::new(33, 37, 3, 1));
+}
+
+#[test]
+fn test_token_span_after_macro_expansion() {
+    let source = "<script>\nfn $foo(*a): expanded *a\n</script>\nline\n$foo(%1) tail".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    let expanded = tokens
+        .iter()
+        .find(|token| token.value.as_deref() == Some("expanded 1 tail"))
+        .unwrap();
+    assert_eq!(expanded.span.line, 5);
+    assert_eq!(expanded.span.column, 1);
+    assert_eq!(expanded.span.end, 62);
+}
+
+#[test]
+fn test_node_span() {
+    let source = "first line\n# heading \\(~inner)".to_string();
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let document = Parser::new(tokens).parse().unwrap();
+
+    let heading = &document.nodes[1][0];
+    assert_eq!(heading.span(), Span::new(11, 30, 2, 1));
+    let crate::parse::node::ASTNode::Inline { content, .. } = heading else {
+        panic!("Expected an inline heading");
+    };
+    assert_eq!(content[1].span().line, 2);
+    assert_eq!(content[1].span().column, 13);
+}
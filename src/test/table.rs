@@ -132,3 +132,26 @@ fn test_consecutive_merge() {
     let output = document.build();
     assert!(output.contains(r#"colspan="3""#));
 }
+
+#[test]
+fn test_merge_errors_point_at_the_row() {
+    for (src, message, line) in [
+        (
+            "text\n--- table!\n[A;B]\n/// skipped\n\nx;y\n_;z\n---",
+            "Row merge `_` with no left neighbor",
+            7,
+        ),
+        (
+            "--- table!\nA\nx;^\n---",
+            "Column merge `^` with no upper neighbor",
+            3,
+        ),
+    ] {
+        let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+        let document = Parser::new(tokens).parse_with_recovery().unwrap();
+        let error = document.diagnostics.first_error().unwrap();
+        assert_eq!(error.message, message);
+        let span = error.span.unwrap();
+        assert_eq!((span.line, span.column), (line, 1), "{}", src);
+    }
+}
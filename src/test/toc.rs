@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use super::{body, parse};
+use crate::lexer::lexer::Lexer;
+use crate::lexer::token::TokenKind;
+use crate::lexer::traits::LexerTrait;
+use crate::parse::meta::MetaProperties;
+use crate::parse::node::{ASTNode, TocEntry};
+use crate::parse::toc::slugify;
+
+#[test]
+fn test_toc_token() {
+    for (src, value) in [
+        ("<toc />", ""),
+        ("<toc>", ""),
+        ("<toc depth=2 />", " depth=2"),
+    ] {
+        let tokens = Lexer::new(src.to_string()).tokenize().unwrap();
+        assert_eq!(tokens[0].kind, TokenKind::TableOfContents, "{}", src);
+        assert_eq!(tokens[0].value.as_deref(), Some(value), "{}", src);
+    }
+}
+
+#[test]
+fn test_slugify() {
+    assert_eq!(slugify("Hello World"), "hello-world");
+    assert_eq!(slugify("2. Proof: Part B"), "2-proof-part-b");
+    assert_eq!(slugify("  Setup & install -- now "), "setup-install-now");
+    assert_eq!(slugify("Ünïcode Straße"), "ünïcode-straße");
+    assert_eq!(slugify("???"), "section");
+}
+
+#[test]
+fn test_heading_anchors() {
+    let html = body("# Intro\n## Setup\n## Setup\n# **Bold** and `code`");
+    assert!(html.contains(r#"<h1 id="intro" class="h1size">"#));
+    assert!(html.contains(r#"<h2 id="setup" class="h2size">"#));
+    assert!(html.contains(r#"<h2 id="setup-1" class="h2size">"#));
+    assert!(html.contains(r#"<h1 id="bold-and-code" class="h1size">"#));
+}
+
+#[test]
+fn test_nested_toc() {
+    let html = body("<toc />\n# One\n## One A\n### One A i\n## One B\n# Two");
+    assert!(html.contains(concat!(
+        r##"<nav class="toc"><ol><li><a href="#one">One</a><ol>"##,
+        r##"<li><a href="#one-a">One&nbsp;A</a><ol><li><a href="#one-a-i">One&nbsp;A&nbsp;i</a></li></ol></li>"##,
+        r##"<li><a href="#one-b">One&nbsp;B</a></li></ol></li>"##,
+        r##"<li><a href="#two">Two</a></li></ol></nav><h1"##,
+    )));
+}
+
+#[test]
+fn test_default_depth() {
+    let html = body("<toc />\n# A\n## B\n### C\n#### D");
+    assert!(html.contains(r##"href="#c""##));
+    assert!(!html.contains(r##"href="#d""##));
+}
+
+#[test]
+fn test_depth_from_placeholder_and_meta() {
+    let html = body("<toc depth=1 />\n# A\n## B");
+    assert!(!html.contains(r##"href="#b""##));
+    let html = body("<meta toc-depth=4 />\n<toc />\n# A\n#### D");
+    assert!(html.contains(r##"href="#d""##));
+    let html = body("<meta toc-depth=1 />\n<toc depth=2 />\n# A\n## B");
+    assert!(html.contains(r##"href="#b""##));
+}
+
+#[test]
+fn test_meta_toc_opens_document() {
+    let document = parse("<meta toc=true />\nText\n# A");
+    assert!(matches!(
+        &document.nodes[0][0],
+        ASTNode::TableOfContents { entries, .. } if entries == &vec![TocEntry {
+            level: 1,
+            anchor: String::from("a"),
+            text: String::from("A"),
+        }]
+    ));
+    let html = body("<meta toc=true />\n<toc />\n# A");
+    assert_eq!(html.matches("<nav").count(), 1);
+    assert!(!body("<meta toc=false />\n# A").contains("<nav"));
+}
+
+#[test]
+fn test_container_headings_anchored_not_listed() {
+    let html = body("<toc />\n# Outside\n--- note!\n# Inside\n---");
+    assert!(html.contains(r#"<h1 id="inside" class="h1size">"#));
+    assert!(!html.contains(r##"href="#inside""##));
+}
+
+#[test]
+fn test_toc_meta_properties() {
+    let result = MetaProperties::new(String::from("toc=true"));
+    assert_eq!(result.unwrap(), MetaProperties::Toc(true));
+    let result = MetaProperties::new(String::from("toc-depth=2"));
+    assert_eq!(result.unwrap(), MetaProperties::TocDepth(2));
+    assert!(MetaProperties::new(String::from("toc=yes")).is_err());
+    assert!(MetaProperties::new(String::from("toc-depth=7")).is_err());
+    assert!(MetaProperties::new(String::from("toc-depth=0")).is_err());
+}
@@ -1,5 +1,6 @@
 #![cfg(test)]
 
+use crate::lexer::span::Span;
 use crate::parse::meta::MetaProperties;
 use crate::parse::node::ASTNode;
 use crate::parse::node::BlockedContent;
@@ -10,7 +11,7 @@ fn test_document_debug() {
     let document = Document::init();
     assert_eq!(
         format!("{:?}", document),
-        "Document { meta: [], nodes: [] }"
+        "Document { meta: [], nodes: [], footnotes: Footnotes { references: [], notes: [] }, diagnostics: Diagnostics { entries: [] } }"
     );
 }
 
@@ -34,6 +35,7 @@ fn test_document_append_node() {
     let mut document = Document::init();
     document.append_node(vec![ASTNode::BlockedContent {
         content: BlockedContent::PlainText("Test".to_string()),
+        span: Span::default(),
     }]);
     assert_eq!(document.meta.len(), 0);
     assert_eq!(document.nodes.len(), 1);
@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq)]
+use crate::utilities::error::ArcError;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ColorLiteral {
     Red,
     Orange,
@@ -20,16 +22,16 @@ pub enum ColorLiteral {
     Maroon,
     Navy,
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Color {
     Literal(ColorLiteral),
-    RGB(u8, u8, u8),
+    Rgb(u8, u8, u8),
 }
 
 impl Color {
     pub fn build(&self) -> String {
         match self {
-            Color::RGB(r, g, b) => format!("rgb({}, {}, {})", r, g, b),
+            Color::Rgb(r, g, b) => format!("rgb({}, {}, {})", r, g, b),
             Color::Literal(_) => {
                 let (r, g, b) = self.to_rgb();
                 format!("rgb({}, {}, {})", r, g, b)
@@ -37,9 +39,13 @@ impl Color {
         }
     }
 
-    pub fn from_string(string: String) -> Result<Self, String> {
-        if string.starts_with("(") {
-            let mut rgb: std::str::Split<'_, &str> = string[1..string.len() - 1].split(",");
+    pub fn from_string(string: String) -> Result<Self, ArcError> {
+        if let Some(inner) = string.strip_prefix("(") {
+            let Some(inner) = inner.strip_suffix(")") else {
+                return Err(ArcError::style(format!("Unclosed rgb literal: {}", string))
+                    .with_help("rgb literals are written as `(255, 0, 0)`"));
+            };
+            let mut rgb: std::str::Split<'_, &str> = inner.split(",");
             if rgb.clone().count() == 3 {
                 let r = Self::parse_rgb_literal(&mut rgb)?;
                 let g = Self::parse_rgb_literal(&mut rgb)?;
@@ -48,16 +54,22 @@ impl Color {
             }
 
             if rgb.clone().count() > 3 {
-                return Err(format!("Too many values for rgb literal: {}", string));
+                Err(ArcError::style(format!(
+                    "Too many values for rgb literal: {}",
+                    string
+                )))
             } else {
-                return Err(format!("Insufficient values for rgb literal: {}", string));
+                Err(ArcError::style(format!(
+                    "Insufficient values for rgb literal: {}",
+                    string
+                )))
             }
         } else {
             Ok(Color::new_literal(Self::parse_str_literal(&string)?))
         }
     }
 
-    fn parse_str_literal(string: &String) -> Result<ColorLiteral, String> {
+    fn parse_str_literal(string: &String) -> Result<ColorLiteral, ArcError> {
         match string.to_lowercase().trim() {
             "red" => Ok(ColorLiteral::Red),
             "orange" => Ok(ColorLiteral::Orange),
@@ -78,15 +90,22 @@ impl Color {
             "teal" => Ok(ColorLiteral::Teal),
             "maroon" => Ok(ColorLiteral::Maroon),
             "navy" => Ok(ColorLiteral::Navy),
-            _ => Err(format!("Invalid color literal: {}", string)),
+            _ => Err(
+                ArcError::style(format!("Invalid color literal: {}", string)).with_help(
+                    "use a color name such as `red` or an rgb literal such as `(255, 0, 0)`",
+                ),
+            ),
         }
     }
 
-    fn parse_rgb_literal(rgb: &mut std::str::Split<'_, &str>) -> Result<u8, String> {
+    fn parse_rgb_literal(rgb: &mut std::str::Split<'_, &str>) -> Result<u8, ArcError> {
         let value = rgb.next().unwrap();
         let parsed_value = value.trim().parse::<u8>();
         if parsed_value.is_err() {
-            return Err(format!("Invalid value for rgb literal: {}", value));
+            return Err(ArcError::style(format!(
+                "Invalid value for rgb literal: {}",
+                value
+            )));
         }
         Ok(parsed_value.unwrap())
     }
@@ -114,12 +133,12 @@ impl Color {
                 ColorLiteral::Maroon => (128, 0, 0),
                 ColorLiteral::Navy => (0, 0, 128),
             },
-            Color::RGB(r, g, b) => (*r, *g, *b),
+            Color::Rgb(r, g, b) => (*r, *g, *b),
         }
     }
 
     fn new_rgb(r: u8, g: u8, b: u8) -> Self {
-        Self::RGB(r, g, b)
+        Self::Rgb(r, g, b)
     }
     fn new_literal(literal: ColorLiteral) -> Self {
         Self::Literal(literal)
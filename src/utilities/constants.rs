@@ -1,23 +1,35 @@
-pub const STRING_REGEX: &str = r"(?:(?!\*\*|\\\(|\\\)|<math\b[^>]*\/>)[^\)\n])+";
+// info: plain text stops where a link, style, footnote or other inline token would
+// match, and only there, so `&[` or `%[` that start nothing stay text
+pub const STRING_REGEX: &str = r"(?:(?!\*\*|\\\(|\\\)|<math\b[^>]*\/>|`[^`\n]+`|\[\^[^\]\s]+\]|%\[[^\n]*?\]|&>?\[(?:https?:\/\/|mailto:|tel:|(?![a-zA-Z][a-zA-Z0-9+.-]*:(?!\d)))[^\]\s']+(?: '[^'\n]*')?\])[^\)\n])+";
 pub const NEWLINE_REGEX: &str = r"\n";
 pub const WHITESPACE_REGEX: &str = r"\s+";
-pub const LINK_REGEX: &str = r"&\[((?:https?:\/\/)?[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}(?:\/[^\s]*)*)\] ?";
+pub const IMAGE_REGEX: &str = r"!\[([^\]\n]+)\] ?(\(\s*\d+(?:\.\d+)?\s*,\s*\d+(?:\.\d+)?\s*\))? ?'([^'\n]*)'(?: ?'([^'\n]*)')? ?";
+pub const LINK_REGEX: &str = r"&>?\[((?:https?:\/\/|mailto:|tel:)[^\]\s']+|(?![a-zA-Z][a-zA-Z0-9+.-]*:(?!\d))[^\]\s']+)(?: '([^'\n]*)')?\] ?";
+pub const HOST_PORT_REGEX: &str = r"^[a-zA-Z0-9.-]+:\d+(?:[\/?#]|$)";
+pub const FOOTNOTE_DEFINITION_REGEX: &str = r"\[\^([^\]\s]+)\]: ?";
+pub const FOOTNOTE_REFERENCE_REGEX: &str = r"\[\^([^\]\s]+)\]";
 pub const DEFINITION_REGEX: &str = r"@\[(.*?)\] ?'(.*?)'";
 pub const CHARACTER_STYLE_REGEX: &str = r"%\[(.*?)\] ?";
 pub const META_DATA_REGEX_SHORT: &str = r"<meta ([^\n]*)>";
 pub const META_DATA_REGEX_LONG: &str = r"<meta ([^\n]*)\/>";
+pub const TOC_REGEX: &str = r"<toc((?:\s+depth=\d)?)\s*\/?>";
 pub const LITERAL_RIGHT_PARENTHESIS_REGEX: &str = r"\\\)";
 pub const BACKSLASH_LEFT_PARENTHESIS_INLINE_REGEX: &str = r"\\\(";
-pub const BOLD_REGEX: &str = r"\*\*(.*?)\*\*";
-pub const HEADING_REGEX: &str = r"(#{1,4}) ";
-pub const ORDERED_LIST_REGEX: &str = r"\d+\. ";
-pub const UNORDERED_LIST_REGEX: &str = r"- ";
+pub const BOLD_REGEX: &str = r"\*\*((?:`[^`\n]+`|.)*?)\*\*";
+pub const HEADING_REGEX: &str = r"(#{1,6}) ";
+pub const ORDERED_LIST_REGEX: &str =
+    r"([ \t]*(?:\d+\.|[ivx]{2,4}\.|[IVX]{1,4}\.|[a-z]\.|\(\d+\))) ";
+pub const UNORDERED_LIST_REGEX: &str = r"([ \t]*-(?: \[[ xX]\])?) ";
+pub const BLOCKQUOTE_REGEX: &str = r"((?:> ?)*>(?: --(?= ))?)(?: |(?=\n)|$)";
 pub const ITALIC_REGEX: &str = r"(?<!~)~(?!~) ?";
+pub const STRIKETHROUGH_REGEX: &str = r"~~(?!~) ?";
+pub const UNDERLINE_REGEX: &str = r"__(?!_) ?";
+pub const SUPERSCRIPT_REGEX: &str = r"\^\^(?!\^) ?";
+pub const SUBSCRIPT_REGEX: &str = r",,(?!,) ?";
 pub const RIGHT_PARENTHESIS_REGEX: &str = r"\)";
 pub const CRLF_REGEX: &str = r"\\[\s]*\n";
 pub const COMMENT_REGEX: &str = r"\n?///.*";
 pub const TABLE_CONTAINER_REGEX: &str = r"(?ms)^---\s*table!\s*\n(?P<content>.*?)\n---\s*$";
-pub const MULTIPLE_NEWLINE_REGEX: &str = r"\n{2,}";
 pub const WIDTH_HEIGHT_REGEX: &str = r"\(\s*(\d+(?:\.\d+)?)\s*,\s*(\d+(?:\.\d+)?)\s*\)";
 pub const INLINE_MATH_REGEX: &str = r"<math\s+(?<content>.*?)\s*\/\s*>";
 pub const BLOCK_MATH_REGEX: &str = r"<math>\s*(?<content>[\s\S]*?)\s*</math>";
@@ -26,7 +38,7 @@ pub const ANTI_META_REGEX: &str = r"<body>(<br \/>)+";
 pub const SCRIPT_REGEX: &str = r"<script>([\s\S]*?)</script>";
 pub const SHORT_FUNC_REGEX: &str = r"\|\*([^|]*)\|\s*(.*)";
 pub const FULL_FUNC_REGEX: &str = r"fn (\S+)\s*\(([^)]*)\):\s*(.*)";
-pub const NAME_REGEX: &str = r"<meta\s+name\s*=\s*([^>\s]+)\s*\/?>";
+pub const INLINE_CODE_REGEX: &str = r"`([^`\n]+)`";
 pub const CODE_BLOCK_REGEX: &str = r"<code>([^\n]*)\n([\s\S]*?)<\/code>";
 pub const CODE_LANGUAGE_REGEX: &str = r":([^'\s]+)(?:\s+'([^']*)')?[\s]*";
 pub const HTML_CONTAINER_REGEX: &str = r"(?ms)^---\s*html!\s*\n(?P<content>.*?)\n---\s*$";
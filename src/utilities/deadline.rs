@@ -0,0 +1,58 @@
+use super::error::ArcError;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A cooperative time limit shared by the lexer, the function processor and the parser.
+/// Each stage checks it once per step, so a runaway loop on bad syntax ends with an
+/// error instead of spinning on a detached thread.
+#[derive(Clone, Copy)]
+pub struct Deadline {
+    started: Instant,
+    limit: Option<Duration>,
+}
+
+impl Deadline {
+    /// A deadline that never passes.
+    pub fn none() -> Self {
+        Self {
+            started: Instant::now(),
+            limit: None,
+        }
+    }
+
+    pub fn after(limit: Duration) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::none()
+        }
+    }
+
+    pub fn new(limit: Option<Duration>) -> Self {
+        match limit {
+            Some(limit) => Self::after(limit),
+            None => Self::none(),
+        }
+    }
+
+    pub fn check(&self) -> Result<(), ArcError> {
+        match self.limit {
+            Some(limit) if self.started.elapsed() >= limit => Err(ArcError::timeout(format!(
+                "Compilation timed out after {:?}",
+                limit
+            ))
+            .with_help(
+                "this usually means unbalanced `\\(` and `)`, raise the limit with `--timeout <MS>` or disable it with `--timeout 0`",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limit {
+            Some(limit) => write!(f, "Deadline({:?})", limit),
+            None => write!(f, "Deadline(none)"),
+        }
+    }
+}
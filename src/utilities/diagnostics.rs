@@ -0,0 +1,51 @@
+use super::error::{ArcError, Severity};
+
+/// Collects the warnings and recovered errors raised while compiling a document,
+/// so callers decide whether to print, serialize or reject them.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    entries: Vec<ArcError>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn warn(&mut self, warning: ArcError) {
+        self.entries.push(warning.warning());
+    }
+
+    /// Records an error the caller recovered from.
+    pub fn error(&mut self, error: ArcError) {
+        self.entries.push(ArcError {
+            severity: Severity::Error,
+            ..error
+        });
+    }
+
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ArcError> {
+        self.entries.iter()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ArcError> {
+        self.iter().filter(|e| e.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ArcError> {
+        self.iter().filter(|e| e.severity == Severity::Warning)
+    }
+
+    /// The first recorded error, for callers that stop at a single failure.
+    pub fn first_error(&self) -> Option<ArcError> {
+        self.errors().next().cloned()
+    }
+}
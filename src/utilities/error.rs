@@ -0,0 +1,274 @@
+use crate::lexer::span::Span;
+use colored::Colorize as _;
+use std::fmt;
+
+/// The stage of the pipeline an error comes from. Each kind has its own error code.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ErrorKind {
+    Lexer,
+    Parser,
+    Function,
+    Meta,
+    Style,
+    Table,
+    Io,
+    Timeout,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArcError {
+    pub kind: ErrorKind,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Option<Span>,
+    pub help: Option<String>,
+}
+
+impl ErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Lexer => "E0100",
+            ErrorKind::Parser => "E0200",
+            ErrorKind::Function => "E0300",
+            ErrorKind::Meta => "E0400",
+            ErrorKind::Style => "E0500",
+            ErrorKind::Table => "E0600",
+            ErrorKind::Io => "E0700",
+            ErrorKind::Timeout => "E0800",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::Lexer => "lexer",
+            ErrorKind::Parser => "parser",
+            ErrorKind::Function => "function",
+            ErrorKind::Meta => "meta",
+            ErrorKind::Style => "style",
+            ErrorKind::Table => "table",
+            ErrorKind::Io => "io",
+            ErrorKind::Timeout => "timeout",
+        }
+    }
+}
+
+impl ArcError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity: Severity::Error,
+            message: message.into(),
+            span: None,
+            help: None,
+        }
+    }
+
+    pub fn lexer(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Lexer, message)
+    }
+
+    pub fn parser(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parser, message)
+    }
+
+    pub fn function(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Function, message)
+    }
+
+    pub fn meta(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Meta, message)
+    }
+
+    pub fn style(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Style, message)
+    }
+
+    pub fn table(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Table, message)
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Io, message)
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Timeout, message)
+    }
+
+    /// Attaches a location, unless a more precise one was already recorded.
+    pub fn at(mut self, span: Span) -> Self {
+        self.span.get_or_insert(span);
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+
+    /// Serializes the diagnostic as a single-line JSON object.
+    pub fn to_json(&self, file: Option<&str>) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let mut fields = vec![
+            format!(r#""severity":"{}""#, severity),
+            format!(r#""code":"{}""#, self.code()),
+            format!(r#""kind":"{}""#, self.kind.name()),
+            format!(r#""message":{}"#, json_string(&self.message)),
+        ];
+        if let Some(file) = file {
+            fields.push(format!(r#""file":{}"#, json_string(file)));
+        }
+        if let Some(span) = self.span {
+            fields.push(format!(
+                r#""span":{{"start":{},"end":{},"line":{},"column":{}}}"#,
+                span.start, span.end, span.line, span.column
+            ));
+        }
+        if let Some(help) = &self.help {
+            fields.push(format!(r#""help":{}"#, json_string(help)));
+        }
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+impl fmt::Display for ArcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ArcError {}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => output.push_str(r#"\""#),
+            '\\' => output.push_str(r"\\"),
+            '\n' => output.push_str(r"\n"),
+            '\r' => output.push_str(r"\r"),
+            '\t' => output.push_str(r"\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!(r"\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// An error together with the file it came from, rendered like a compiler diagnostic.
+#[derive(Debug)]
+pub struct Report {
+    pub error: Box<ArcError>,
+    source: Option<(String, String)>,
+}
+
+impl Report {
+    pub fn with_source(mut self, name: impl Into<String>, text: impl Into<String>) -> Self {
+        self.source = Some((name.into(), text.into()));
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let error = &self.error;
+        let header = match error.severity {
+            Severity::Error => format!("error[{}]", error.code()).red().bold(),
+            Severity::Warning => format!("warning[{}]", error.code()).yellow().bold(),
+        };
+        let mut output = format!("{}: {}", header, error.message.bold());
+
+        if let (Some(span), Some((name, text))) = (error.span, &self.source) {
+            let line = text.lines().nth(span.line.saturating_sub(1)).unwrap_or("");
+            let gutter = " ".repeat(span.line.to_string().len());
+            let width = text
+                .get(span.start..span.end)
+                .and_then(|s| s.lines().next())
+                .map_or(1, |s| s.chars().count().max(1));
+            let marker = format!(
+                "{}{}",
+                " ".repeat(span.column.saturating_sub(1)),
+                "^".repeat(width)
+            );
+            output.push_str(&format!(
+                "\n{}{} {}:{}:{}",
+                gutter,
+                "-->".blue().bold(),
+                name,
+                span.line,
+                span.column
+            ));
+            output.push_str(&format!("\n{} {}", gutter, "|".blue().bold()));
+            output.push_str(&format!(
+                "\n{} {} {}",
+                span.line.to_string().blue().bold(),
+                "|".blue().bold(),
+                line
+            ));
+            output.push_str(&format!(
+                "\n{} {} {}",
+                gutter,
+                "|".blue().bold(),
+                match error.severity {
+                    Severity::Error => marker.red().bold(),
+                    Severity::Warning => marker.yellow().bold(),
+                }
+            ));
+        }
+
+        if let Some(help) = &error.help {
+            output.push_str(&format!("\n  {} {}", "= help:".bold(), help));
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        let file = self.source.as_ref().map(|(name, _)| name.as_str());
+        self.error.to_json(file)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+impl From<ArcError> for Report {
+    fn from(error: ArcError) -> Self {
+        Self {
+            error: Box::new(error),
+            source: None,
+        }
+    }
+}
+
+impl From<String> for Report {
+    fn from(message: String) -> Self {
+        ArcError::io(message).into()
+    }
+}
+
+impl From<&str> for Report {
+    fn from(message: &str) -> Self {
+        ArcError::io(message).into()
+    }
+}
@@ -1,3 +1,10 @@
 pub mod ce;
 pub mod fmt;
 pub mod math;
+
+/// The standard library files `arc write` installs, by file stem.
+pub const STD_LIB: [(&str, &str); 3] = [
+    ("fmt", fmt::FMT_CONTENT),
+    ("math", math::MATH_CONTENT),
+    ("ce", ce::CE_CONTENT),
+];
@@ -1,5 +1,9 @@
 pub mod color;
 pub mod constants;
+pub mod deadline;
+pub mod diagnostics;
+pub mod error;
+pub mod regexes;
 pub mod stdout;
 pub mod style;
 
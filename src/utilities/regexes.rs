@@ -0,0 +1,22 @@
+use super::constants::*;
+use fancy_regex::Regex;
+use std::sync::LazyLock;
+
+// Hard-coded regexes are compiled once per process, on first use.
+
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("Hard coded regex should be valid.")
+}
+
+pub static CRLF: LazyLock<Regex> = LazyLock::new(|| compile(CRLF_REGEX));
+pub static COMMENT: LazyLock<Regex> = LazyLock::new(|| compile(COMMENT_REGEX));
+pub static SCRIPT: LazyLock<Regex> = LazyLock::new(|| compile(SCRIPT_REGEX));
+pub static IMPORT: LazyLock<Regex> = LazyLock::new(|| compile(IMPORT_REGEX));
+pub static FULL_FUNC: LazyLock<Regex> = LazyLock::new(|| compile(FULL_FUNC_REGEX));
+pub static SHORT_FUNC: LazyLock<Regex> = LazyLock::new(|| compile(SHORT_FUNC_REGEX));
+pub static MULTI_LINE_FN: LazyLock<Regex> = LazyLock::new(|| compile(MULTI_LINE_FN_REGEX));
+pub static INLINE_CODE: LazyLock<Regex> = LazyLock::new(|| compile(INLINE_CODE_REGEX));
+pub static CODE_LANGUAGE: LazyLock<Regex> = LazyLock::new(|| compile(CODE_LANGUAGE_REGEX));
+pub static WIDTH_HEIGHT: LazyLock<Regex> = LazyLock::new(|| compile(WIDTH_HEIGHT_REGEX));
+pub static ANTI_META: LazyLock<Regex> = LazyLock::new(|| compile(ANTI_META_REGEX));
+pub static HOST_PORT: LazyLock<Regex> = LazyLock::new(|| compile(HOST_PORT_REGEX));
@@ -1,18 +1,16 @@
+use crate::utilities::error::Report;
 use colored::Colorize as _;
 
-#[macro_export]
-macro_rules! warn {
-    ($fmt:expr $(, $($arg:tt)*)?) => {
-        #[allow(unused_imports)]
-        use colored::Colorize as _;
-        let formatted_message = format!($fmt $(, $($arg)*)?);
-        println!("{}", formatted_message.bold().yellow())
-    };
+pub fn show_err<E>(res: Result<E, impl Into<Report>>) {
+    if let Err(err) = res {
+        eprintln!("{}", err.into().render());
+        std::process::exit(1);
+    }
 }
-pub fn show_err<E>(res: Result<E, impl ToString>) {
-    use colored::Colorize as _;
+
+pub fn show_err_json<E>(res: Result<E, impl Into<Report>>) {
     if let Err(err) = res {
-        eprintln!("{}", err.to_string().red().bold());
+        println!("{}", err.into().to_json());
         std::process::exit(1);
     }
 }
@@ -22,6 +22,126 @@ ol {
   margin-top: 0;
   margin-bottom: 0;
 }
+li:has(> input[type="checkbox"]) {
+  list-style-type: none;
+}
+blockquote {
+  margin: 0.5em 0;
+  padding-left: 1em;
+  border-left: 0.25em solid #d0d7de;
+}
+blockquote cite {
+  display: block;
+  font-style: normal;
+}
+blockquote cite::before {
+  content: "— ";
+}
+.callout {
+  margin: 0.5em 0;
+  padding: 0.5em 1em;
+  border-left: 0.25em solid var(--callout-color);
+  border-radius: 0.25em;
+  background-color: var(--callout-background);
+}
+.callout-title {
+  font-weight: bold;
+  color: var(--callout-color);
+}
+.callout-title::before {
+  margin-right: 0.4em;
+}
+.callout-note {
+  --callout-color: #0969da;
+  --callout-background: #ddf4ff;
+}
+.callout-note .callout-title::before {
+  content: "ℹ️";
+}
+.callout-tip {
+  --callout-color: #1a7f37;
+  --callout-background: #dafbe1;
+}
+.callout-tip .callout-title::before {
+  content: "💡";
+}
+.callout-warning {
+  --callout-color: #9a6700;
+  --callout-background: #fff8c5;
+}
+.callout-warning .callout-title::before {
+  content: "⚠️";
+}
+.callout-danger {
+  --callout-color: #cf222e;
+  --callout-background: #ffebe9;
+}
+.callout-danger .callout-title::before {
+  content: "⛔";
+}
+details,
+section.details {
+  margin: 0.5em 0;
+}
+details > summary,
+.details-summary {
+  font-weight: bold;
+}
+details > summary {
+  cursor: pointer;
+}
+.details-body {
+  padding-left: 1em;
+}
+.footnote-ref {
+  line-height: 0;
+}
+.footnote-ref a {
+  text-decoration: none;
+}
+.footnotes {
+  margin-top: 2em;
+  padding-top: 0.5em;
+  border-top: 1px solid #d0d7de;
+  font-size: smaller;
+}
+.footnote-back {
+  margin-left: 0.4em;
+}
+.sidenote {
+  display: none;
+}
+@media (min-width: 1100px), print {
+  body:has(.sidenote) {
+    margin-right: 18rem;
+  }
+  .sidenote {
+    display: block;
+    float: right;
+    clear: right;
+    width: 14rem;
+    margin-right: -16rem;
+    font-size: smaller;
+  }
+  .sidenote > sup {
+    margin-right: 0.3em;
+  }
+  .footnotes.with-sidenotes {
+    display: none;
+  }
+}
+nav.toc ol {
+  list-style-type: none;
+  padding-left: 1.2em;
+}
+nav.toc > ol {
+  padding-left: 0;
+}
+@counter-style parenthesized {
+  system: extends decimal;
+  prefix: "(";
+  suffix: ") ";
+}
 body {
   color: var(--text-color);
   line-height: 1.6;
@@ -45,6 +165,14 @@ span {
   word-break: normal;
   hyphens: auto;
 }
+h1,
+h2,
+h3,
+h4,
+h5,
+h6 {
+  margin: 0;
+}
 .h1size {
   font-size: 2em;
 }
@@ -57,10 +185,18 @@ span {
 .h4size {
   font-size: 1.125em;
 }
+.h5size {
+  font-size: 1em;
+}
+.h6size {
+  font-size: 0.875em;
+}
 .h1size span,
 .h2size span,
 .h3size span,
-.h4size span {
+.h4size span,
+.h5size span,
+.h6size span {
   font-size: inherit !important;
   color: inherit !important;
   background-color: inherit !important;
@@ -68,7 +204,9 @@ span {
 .h1size a,
 .h2size a,
 .h3size a,
-.h4size a {
+.h4size a,
+.h5size a,
+.h6size a {
   font-size: inherit !important;
   background-color: inherit !important;
 }
@@ -117,3 +255,25 @@ code span {
   font-variant-ligatures: common-ligatures !important;
 }
 "#;
+
+/// The stylesheet embedded in a rendered page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Theme {
+    /// Arc's own stylesheet.
+    #[default]
+    Default,
+    /// A stylesheet supplied by the caller, in place of the default one.
+    Custom(String),
+    /// No stylesheet, for pages that bring their own.
+    None,
+}
+
+impl Theme {
+    pub fn css(&self) -> &str {
+        match self {
+            Theme::Default => STYLE,
+            Theme::Custom(css) => css,
+            Theme::None => "",
+        }
+    }
+}